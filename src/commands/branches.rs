@@ -0,0 +1,472 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use tracing::info;
+
+use super::results::{self, ProjectResult};
+use super::selection::ProjectSelectionArgs;
+use crate::gitlab::client::GitLabClient;
+use crate::models::branch::NewProtectedBranch;
+use crate::models::project::Project;
+use crate::models::user::AccessLevel;
+
+#[derive(Args)]
+pub struct BranchesCommands {
+    #[command(subcommand)]
+    command: BranchesSubcommands,
+}
+
+#[derive(Subcommand)]
+enum BranchesSubcommands {
+    /// Create a branch in projects where it does not exist yet
+    Create(CreateBranchArgs),
+
+    /// Delete a branch from projects
+    Delete(DeleteBranchArgs),
+
+    /// List the branches of projects
+    List(ListBranchesArgs),
+
+    /// Protect a branch or wildcard pattern, or update its protection
+    Protect(ProtectBranchArgs),
+
+    /// Remove the protection of a branch or wildcard pattern
+    Unprotect(BranchArgs),
+}
+
+#[derive(Args)]
+struct CreateBranchArgs {
+    /// Name of the branch to create
+    name: String,
+
+    /// Branch, tag or commit to create the branch from (defaults to the default branch)
+    #[arg(long = "ref", value_name = "REF")]
+    from_ref: Option<String>,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Show what would be done without making changes
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Args)]
+struct DeleteBranchArgs {
+    /// Name of the branch to delete
+    name: String,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Show what would be done without making changes
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Args)]
+struct ListBranchesArgs {
+    /// Only list branches whose name contains this text
+    #[arg(long)]
+    search: Option<String>,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+}
+
+#[derive(Args)]
+struct ProtectBranchArgs {
+    /// Branch name or wildcard pattern, e.g. release/*
+    name: String,
+
+    #[command(flatten)]
+    protection: ProtectionArgs,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Show what would be done without making changes
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// Settings of a protected branch; unset settings use GitLab's defaults when protecting
+/// and are not checked against existing protection
+#[derive(Args)]
+pub struct ProtectionArgs {
+    /// Who may push (no-access, developer, maintainer)
+    #[arg(long, value_name = "ROLE")]
+    push_access_level: Option<AccessLevel>,
+
+    /// Who may merge (no-access, developer, maintainer)
+    #[arg(long, value_name = "ROLE")]
+    merge_access_level: Option<AccessLevel>,
+
+    /// Who may unprotect (developer, maintainer)
+    #[arg(long, value_name = "ROLE")]
+    unprotect_access_level: Option<AccessLevel>,
+
+    /// Require approval from code owners
+    #[arg(long, num_args = 0..=1, default_missing_value = "true", value_name = "BOOL")]
+    code_owner_approval: Option<bool>,
+
+    /// Allow force pushes by those allowed to push
+    #[arg(long, num_args = 0..=1, default_missing_value = "true", value_name = "BOOL")]
+    allow_force_push: Option<bool>,
+}
+
+impl ProtectionArgs {
+    /// The protection to apply to `name`, rejecting levels GitLab does not accept
+    pub fn protection(&self, name: &str) -> Result<NewProtectedBranch> {
+        let level = |label: &str, level: &Option<AccessLevel>, allowed: &[AccessLevel]| match level
+        {
+            Some(level) if !allowed.contains(level) => anyhow::bail!(
+                "{} access level cannot be {}; use one of: {}",
+                label,
+                level,
+                allowed
+                    .iter()
+                    .map(|l| l.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            _ => Ok(level.as_ref().map(AccessLevel::as_u64)),
+        };
+        let writers = [
+            AccessLevel::NoAccess,
+            AccessLevel::Developer,
+            AccessLevel::Maintainer,
+        ];
+
+        Ok(NewProtectedBranch {
+            name: name.to_string(),
+            push_access_level: level("Push", &self.push_access_level, &writers)?,
+            merge_access_level: level("Merge", &self.merge_access_level, &writers)?,
+            unprotect_access_level: level(
+                "Unprotect",
+                &self.unprotect_access_level,
+                &[AccessLevel::Developer, AccessLevel::Maintainer],
+            )?,
+            allow_force_push: self.allow_force_push,
+            code_owner_approval_required: self.code_owner_approval,
+            ..Default::default()
+        })
+    }
+}
+
+#[derive(Args)]
+struct BranchArgs {
+    /// Branch name or wildcard pattern, e.g. release/*
+    name: String,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Show what would be done without making changes
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl BranchesCommands {
+    pub async fn execute(&self, client: &GitLabClient) -> Result<()> {
+        match &self.command {
+            BranchesSubcommands::Create(args) => self.create_branches(client, args).await,
+            BranchesSubcommands::Delete(args) => self.delete_branches(client, args).await,
+            BranchesSubcommands::List(args) => self.list_branches(client, args).await,
+            BranchesSubcommands::Protect(args) => self.protect_branches(client, args).await,
+            BranchesSubcommands::Unprotect(args) => self.unprotect_branches(client, args).await,
+        }
+    }
+
+    async fn create_branches(&self, client: &GitLabClient, args: &CreateBranchArgs) -> Result<()> {
+        info!("Creating branch {}", args.name);
+
+        let projects = args.selection.resolve(client).await?;
+
+        info!("Found {} projects to update", projects.len());
+
+        let mut results = Vec::new();
+
+        for project in &projects {
+            let result = self.create_branch(client, args, project).await;
+            results.push(result.unwrap_or_else(|e| {
+                ProjectResult::failed(&project.path_with_namespace, format!("{:#}", e))
+            }));
+        }
+
+        results::report(&results)
+    }
+
+    async fn create_branch(
+        &self,
+        client: &GitLabClient,
+        args: &CreateBranchArgs,
+        project: &Project,
+    ) -> Result<ProjectResult> {
+        let path = &project.path_with_namespace;
+
+        if client
+            .branches()
+            .get(project.id, &args.name)
+            .await?
+            .is_some()
+        {
+            return Ok(ProjectResult::unchanged(
+                path,
+                format!("{} already exists", args.name),
+            ));
+        }
+
+        let Some(from_ref) = args.from_ref.as_ref().or(project.default_branch.as_ref()) else {
+            return Ok(ProjectResult::skipped(
+                path,
+                "no default branch to create from (is the repository empty?)",
+            ));
+        };
+
+        if args.dry_run {
+            return Ok(ProjectResult::dry_run(
+                path,
+                format!("would create {} from {}", args.name, from_ref),
+            ));
+        }
+
+        let branch = client
+            .branches()
+            .create(project.id, &args.name, from_ref)
+            .await?;
+
+        Ok(ProjectResult::success(
+            path,
+            format!(
+                "created {} from {} at {}",
+                branch.name,
+                from_ref,
+                &branch.commit.id[..branch.commit.id.len().min(8)]
+            ),
+        ))
+    }
+
+    async fn delete_branches(&self, client: &GitLabClient, args: &DeleteBranchArgs) -> Result<()> {
+        info!("Deleting branch {}", args.name);
+
+        let projects = args.selection.resolve(client).await?;
+
+        info!("Found {} projects to update", projects.len());
+
+        let mut results = Vec::new();
+
+        for project in &projects {
+            let result = self.delete_branch(client, args, project).await;
+            results.push(result.unwrap_or_else(|e| {
+                ProjectResult::failed(&project.path_with_namespace, format!("{:#}", e))
+            }));
+        }
+
+        results::report(&results)
+    }
+
+    async fn delete_branch(
+        &self,
+        client: &GitLabClient,
+        args: &DeleteBranchArgs,
+        project: &Project,
+    ) -> Result<ProjectResult> {
+        let path = &project.path_with_namespace;
+
+        let Some(branch) = client.branches().get(project.id, &args.name).await? else {
+            return Ok(ProjectResult::skipped(
+                path,
+                format!("{} not found", args.name),
+            ));
+        };
+
+        if branch.default || project.default_branch.as_deref() == Some(branch.name.as_str()) {
+            return Ok(ProjectResult::skipped(
+                path,
+                format!("{} is the default branch", branch.name),
+            ));
+        }
+        if branch.protected {
+            return Ok(ProjectResult::skipped(
+                path,
+                format!("{} is protected; unprotect it first", branch.name),
+            ));
+        }
+
+        if args.dry_run {
+            return Ok(ProjectResult::dry_run(
+                path,
+                format!("would delete {}", branch.name),
+            ));
+        }
+
+        client.branches().delete(project.id, &branch.name).await?;
+
+        Ok(ProjectResult::success(
+            path,
+            format!("deleted {}", branch.name),
+        ))
+    }
+
+    async fn list_branches(&self, client: &GitLabClient, args: &ListBranchesArgs) -> Result<()> {
+        info!("Listing branches");
+
+        let projects = args.selection.resolve(client).await?;
+
+        for project in &projects {
+            println!("{}", project.path_with_namespace.green());
+
+            let branches = client
+                .branches()
+                .list(project.id, args.search.as_deref())
+                .await?;
+
+            if branches.is_empty() {
+                println!("  No branches found.");
+            }
+
+            for branch in &branches {
+                let mut flags = Vec::new();
+                if branch.default {
+                    flags.push("default");
+                }
+                if branch.protected {
+                    flags.push("protected");
+                }
+
+                println!(
+                    "  {} {} {}",
+                    branch.name.cyan(),
+                    &branch.commit.id[..branch.commit.id.len().min(8)],
+                    flags.join(", ").yellow()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn protect_branches(
+        &self,
+        client: &GitLabClient,
+        args: &ProtectBranchArgs,
+    ) -> Result<()> {
+        info!("Protecting branch {}", args.name);
+
+        let protection = args.protection.protection(&args.name)?;
+        let projects = args.selection.resolve(client).await?;
+
+        info!("Found {} projects to update", projects.len());
+
+        let mut results = Vec::new();
+
+        for project in &projects {
+            let result = apply_protection(client, project, &protection, args.dry_run).await;
+            results.push(result.unwrap_or_else(|e| {
+                ProjectResult::failed(&project.path_with_namespace, format!("{:#}", e))
+            }));
+        }
+
+        results::report(&results)
+    }
+
+    async fn unprotect_branches(&self, client: &GitLabClient, args: &BranchArgs) -> Result<()> {
+        info!("Unprotecting branch {}", args.name);
+
+        let projects = args.selection.resolve(client).await?;
+
+        info!("Found {} projects to update", projects.len());
+
+        let mut results = Vec::new();
+
+        for project in &projects {
+            let path = &project.path_with_namespace;
+
+            let result = async {
+                if client
+                    .branches()
+                    .get_protected(project.id, &args.name)
+                    .await?
+                    .is_none()
+                {
+                    return Ok(ProjectResult::unchanged(
+                        path,
+                        format!("{} is not protected", args.name),
+                    ));
+                }
+
+                if args.dry_run {
+                    return Ok(ProjectResult::dry_run(
+                        path,
+                        format!("would unprotect {}", args.name),
+                    ));
+                }
+
+                client.branches().unprotect(project.id, &args.name).await?;
+
+                anyhow::Ok(ProjectResult::success(
+                    path,
+                    format!("unprotected {}", args.name),
+                ))
+            };
+
+            results.push(
+                result
+                    .await
+                    .unwrap_or_else(|e| ProjectResult::failed(path, format!("{:#}", e))),
+            );
+        }
+
+        results::report(&results)
+    }
+}
+
+/// Protect the branch as described. An existing protection that differs is updated in
+/// place with PATCH, keeping its user and group entries.
+pub async fn apply_protection(
+    client: &GitLabClient,
+    project: &Project,
+    protection: &NewProtectedBranch,
+    dry_run: bool,
+) -> Result<ProjectResult> {
+    let path = &project.path_with_namespace;
+    let name = &protection.name;
+    let branches = client.branches();
+
+    let Some(current) = branches.get_protected(project.id, name).await? else {
+        if dry_run {
+            return Ok(ProjectResult::dry_run(
+                path,
+                format!("would protect {}", name),
+            ));
+        }
+        branches.protect(project.id, protection).await?;
+        return Ok(ProjectResult::success(path, format!("protected {}", name)));
+    };
+
+    let differences = protection.differences(&current);
+    if differences.is_empty() {
+        return Ok(ProjectResult::unchanged(
+            path,
+            format!("{} is already protected as requested", name),
+        ));
+    }
+
+    if dry_run {
+        return Ok(ProjectResult::dry_run(
+            path,
+            format!("would update {}: {}", name, differences.join(", ")),
+        ));
+    }
+
+    // Update in place, so the branch is never unprotected and user and group entries stay
+    branches
+        .update_protected(project.id, name, &protection.update_for(&current))
+        .await?;
+
+    Ok(ProjectResult::success(
+        path,
+        format!("updated {}: {}", name, differences.join(", ")),
+    ))
+}
@@ -1,10 +1,30 @@
 use anyhow::{Context, Result};
 use clap::{Args, Subcommand};
-use std::path::PathBuf;
-use tracing::{debug, info};
+use colored::Colorize;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
 
+use super::results::{self, ProjectResult};
+use super::selection::{ProjectSelectionArgs, resolve_group};
 use crate::gitlab::client::GitLabClient;
-use crate::utils::csv::CsvReader;
+use crate::models::commit::{CommitAction, CommitActionKind, CommitDetails, NewCommit};
+use crate::models::file::{FileConflict, content_sha256};
+use crate::models::merge_request::NewMergeRequest;
+use crate::models::project::Project;
+use crate::models::search::{self, BlobMatch, SearchHit};
+use crate::utils::csv::CsvWriter;
+use crate::utils::diff;
+use crate::utils::edits::{self, Edit};
+use crate::utils::guards::Guards;
+use crate::utils::structured::{self, Format};
+use crate::utils::template;
+use crate::utils::text_style;
 
 #[derive(Args)]
 pub struct FileCommands {
@@ -15,188 +35,1956 @@ pub struct FileCommands {
 #[derive(Subcommand)]
 enum FileSubcommands {
     /// Add or update files in repositories
-    Update(UpdateFileArgs),
+    Update(Box<UpdateFileArgs>),
+
+    /// Show how the local files differ from each repository's version
+    Diff(Box<DiffFileArgs>),
+
+    /// Edit each repository's own version of a file in place
+    Edit(Box<EditFileArgs>),
+
+    /// Set a key in each repository's YAML, JSON or TOML file
+    SetKey(Box<SetKeyArgs>),
+
+    /// Delete files from repositories
+    Delete(Box<DeleteFileArgs>),
+
+    /// Move or rename a file in repositories
+    Move(Box<MoveFileArgs>),
+
+    /// Download a file from every repository into a local directory
+    Get(Box<GetFileArgs>),
+
+    /// Search file contents across repositories
+    Search(Box<SearchFileArgs>),
 }
 
 #[derive(Args)]
-struct UpdateFileArgs {
+struct FileSourceArgs {
     /// Path to local file to upload
-    #[arg(short, long)]
-    file_path: PathBuf,
+    #[arg(short, long, requires = "target_path", conflicts_with_all = ["files", "dir"])]
+    file_path: Option<PathBuf>,
 
-    /// Target path in the repository
+    /// Target path in the repository (the target directory when used with --dir)
     #[arg(short, long)]
-    target_path: String,
+    target_path: Option<String>,
+
+    /// Local file and repository path pair ("local/path:repo/path"); can be repeated
+    #[arg(long = "file", value_name = "SRC:DEST", conflicts_with = "dir")]
+    files: Vec<String>,
+
+    /// Local directory whose files are all uploaded in a single commit
+    #[arg(long)]
+    dir: Option<PathBuf>,
+
+    #[command(flatten)]
+    edits: EditArgs,
+
+    /// Upload the local line endings, byte order mark and final newline as they are
+    /// instead of matching the repository's version of each file
+    #[arg(long)]
+    no_normalize: bool,
+}
+
+#[derive(Args)]
+struct EditArgs {
+    /// Replace every occurrence of a literal string; can be repeated
+    #[arg(long, num_args = 2, value_names = ["FIND", "REPLACE"])]
+    replace: Vec<String>,
+
+    /// Replace every match of a regex, with $1 or ${name} for capture groups; can be repeated
+    #[arg(long, num_args = 2, value_names = ["PATTERN", "REPLACE"])]
+    regex: Vec<String>,
+
+    /// TOML file of [[edit]] entries with `replace` and either `find` or `regex`
+    #[arg(long)]
+    edits_file: Option<PathBuf>,
+
+    /// Render {{project.*}} and {{topic.*}} variables for each project (in the uploaded
+    /// content, or in the replacement text for file edit)
+    #[arg(long)]
+    template: bool,
+}
+
+impl EditArgs {
+    /// Collect the edits in order: edits file, --replace, then --regex
+    fn edits(&self) -> Result<Vec<Edit>> {
+        let mut edits = match &self.edits_file {
+            Some(path) => edits::load_edits_file(path)?,
+            None => Vec::new(),
+        };
+        edits.extend(edits::from_pairs(&self.replace, Edit::literal)?);
+        edits.extend(edits::from_pairs(&self.regex, Edit::regex)?);
+        Ok(edits)
+    }
+}
+
+#[derive(Args)]
+struct UpdateFileArgs {
+    #[command(flatten)]
+    source: FileSourceArgs,
+
+    #[command(flatten)]
+    guards: GuardArgs,
+
+    #[command(flatten)]
+    commit: CommitArgs,
+}
+
+/// Conditions on each repository's current file that must hold for it to be written
+#[derive(Args)]
+struct GuardArgs {
+    /// Only update files that already exist in the repository
+    #[arg(long, conflicts_with = "only_if_missing")]
+    only_if_exists: bool,
+
+    /// Only create files that do not exist in the repository yet
+    #[arg(long, conflicts_with_all = ["if_contains", "if_not_contains"])]
+    only_if_missing: bool,
+
+    /// Only update files whose current content matches this regex
+    #[arg(long, value_name = "REGEX")]
+    if_contains: Option<String>,
+
+    /// Only write files whose current content does not match this regex
+    #[arg(long, value_name = "REGEX")]
+    if_not_contains: Option<String>,
+}
+
+impl GuardArgs {
+    fn compile(&self) -> Result<Guards> {
+        Guards::new(
+            self.only_if_exists,
+            self.only_if_missing,
+            self.if_contains.as_deref(),
+            self.if_not_contains.as_deref(),
+        )
+    }
+}
 
-    /// Commit message
+/// How and where the changed files are committed
+#[derive(Args)]
+struct CommitArgs {
+    /// Commit message; {{project.*}} and {{topic.*}} variables are rendered per project
     #[arg(short, long, default_value = "Update file via gitlab-bulk CLI")]
     commit_message: String,
 
+    /// Author name of the commits (defaults to the token owner)
+    #[arg(long, requires = "author_email")]
+    author_name: Option<String>,
+
+    /// Author email of the commits (defaults to the token owner)
+    #[arg(long)]
+    author_email: Option<String>,
+
+    /// Add a "Change-Id" trailer; without a value one ID is generated for the whole run
+    #[arg(long, num_args = 0..=1, value_name = "ID")]
+    change_id: Option<Option<String>>,
+
+    /// Add a "Refs" trailer pointing to the ticket that requested the change
+    #[arg(long, value_name = "REF")]
+    ticket: Option<String>,
+
+    /// Add a trailer to the commit message, as KEY=VALUE; can be repeated
+    #[arg(long, value_name = "KEY=VALUE", value_parser = parse_trailer)]
+    trailer: Vec<(String, String)>,
+
+    /// Branch name (defaults to the default branch of the repository)
+    #[arg(short, long)]
+    branch: Option<String>,
+
+    /// Create the branch from this ref in projects where it does not exist yet
+    #[arg(long, value_name = "REF", requires = "branch")]
+    create_branch_from: Option<String>,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    #[command(flatten)]
+    merge_request: MergeRequestArgs,
+
+    /// Show the changes that would be made without committing anything
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Args)]
+struct EditFileArgs {
+    /// Path of the file to edit in each repository
+    #[arg(short, long)]
+    target_path: String,
+
+    #[command(flatten)]
+    edits: EditArgs,
+
+    /// Append a line unless the file already contains it; can be repeated
+    #[arg(long)]
+    append_line: Vec<String>,
+
+    /// Remove every line equal to the given one; can be repeated
+    #[arg(long)]
+    remove_line: Vec<String>,
+
+    /// Unified diff to apply to each repository's file before the other edits
+    #[arg(long)]
+    patch: Option<PathBuf>,
+
+    /// Keep line endings and the final newline exactly as the edits leave them
+    #[arg(long)]
+    no_normalize: bool,
+
+    #[command(flatten)]
+    commit: CommitArgs,
+}
+
+#[derive(Args)]
+struct SetKeyArgs {
+    /// Path of the structured file in each repository
+    #[arg(short, long)]
+    target_path: String,
+
+    /// Dot-separated key path, e.g. variables.DOCKER_VERSION (use \. for a literal dot)
+    #[arg(short, long)]
+    key: String,
+
+    /// New value; true/false and numbers are written as such unless --string is set
+    #[arg(long)]
+    value: String,
+
+    /// Always write the value as a string
+    #[arg(long)]
+    string: bool,
+
+    /// File format (detected from the file extension by default)
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+
+    /// Keep line endings and the final newline exactly as the rewritten file has them
+    #[arg(long)]
+    no_normalize: bool,
+
+    #[command(flatten)]
+    commit: CommitArgs,
+}
+
+#[derive(Args)]
+struct DeleteFileArgs {
+    /// Path of the file to delete; can be repeated to delete several files in one commit
+    #[arg(short, long, required = true)]
+    target_path: Vec<String>,
+
+    #[command(flatten)]
+    commit: CommitArgs,
+}
+
+#[derive(Args)]
+struct MoveFileArgs {
+    /// Current path of the file in the repository
+    #[arg(long)]
+    from: String,
+
+    /// New path of the file in the repository
+    #[arg(long)]
+    to: String,
+
+    #[command(flatten)]
+    commit: CommitArgs,
+}
+
+#[derive(Args)]
+struct GetFileArgs {
+    /// Path of the file in each repository
+    #[arg(short, long)]
+    target_path: String,
+
+    /// Directory the files are saved to, as <out-dir>/<project path>/<target path>
+    #[arg(short, long)]
+    out_dir: PathBuf,
+
+    /// CSV index of which projects had the file (defaults to <out-dir>/index.csv)
+    #[arg(long)]
+    index: Option<PathBuf>,
+
     /// Branch name (defaults to the default branch of the repository)
     #[arg(short, long)]
     branch: Option<String>,
 
-    /// Path to CSV file containing project details
-    #[arg(long, conflicts_with = "project_ids")]
-    project_file: Option<PathBuf>,
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+}
+
+/// A row of the `file get` index
+#[derive(Debug, Serialize)]
+struct DownloadRecord {
+    project: String,
+    branch: String,
+    found: bool,
+    local_path: String,
+    size: usize,
+    error: String,
+}
+
+#[derive(Args)]
+struct SearchFileArgs {
+    /// Text to search for
+    pattern: String,
+
+    /// Fetch and search these files instead of using GitLab's blob search; can be repeated
+    #[arg(long = "path")]
+    paths: Vec<String>,
+
+    /// Treat the pattern as a regex (only when searching specific paths)
+    #[arg(long, requires = "paths")]
+    regex: bool,
+
+    /// Search all projects of a group in one go (requires advanced search)
+    #[arg(long, conflicts_with_all = ["global", "paths"])]
+    group: Option<String>,
+
+    /// Search every project on the instance (requires advanced search)
+    #[arg(long, conflicts_with = "paths")]
+    global: bool,
+
+    /// Branch to search (defaults to the default branch of the repository)
+    #[arg(short, long, conflicts_with_all = ["group", "global"])]
+    branch: Option<String>,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Output format (table, json)
+    #[arg(long, default_value = "table", value_parser = ["table", "json"])]
+    format: String,
+}
 
-    /// Comma-separated list of project IDs or paths
-    #[arg(short, long, value_delimiter = ',', conflicts_with = "project_file")]
-    project_ids: Option<Vec<String>>,
+#[derive(Args)]
+struct DiffFileArgs {
+    #[command(flatten)]
+    source: FileSourceArgs,
 
-    /// GitLab topic to filter projects
+    /// Branch name (defaults to the default branch of the repository)
     #[arg(short, long)]
-    topic: Option<String>,
+    branch: Option<String>,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+}
+
+#[derive(Args)]
+struct MergeRequestArgs {
+    /// Commit to a feature branch and open a merge request instead of committing directly
+    #[arg(long)]
+    via_mr: bool,
+
+    /// Name of the feature branch created for the merge request
+    #[arg(long, default_value = "gitlab-cli/update-file", requires = "via_mr")]
+    mr_branch: String,
+
+    /// Merge request title (defaults to the commit message)
+    #[arg(long, requires = "via_mr")]
+    mr_title: Option<String>,
+
+    /// Merge request description
+    #[arg(long, requires = "via_mr")]
+    mr_description: Option<String>,
+
+    /// Comma-separated list of labels for the merge request
+    #[arg(long, value_delimiter = ',', requires = "via_mr")]
+    mr_labels: Option<Vec<String>>,
+
+    /// Comma-separated list of usernames to assign the merge request to
+    #[arg(long, value_delimiter = ',', requires = "via_mr")]
+    mr_assignees: Option<Vec<String>>,
+
+    /// Comma-separated list of usernames to request reviews from
+    #[arg(long, value_delimiter = ',', requires = "via_mr")]
+    mr_reviewers: Option<Vec<String>>,
+
+    /// Merge automatically once the pipeline succeeds
+    #[arg(long, requires = "via_mr")]
+    auto_merge: bool,
+}
+
+/// A file to write to each repository
+#[derive(Clone)]
+struct PendingFile {
+    target_path: String,
+    content: Vec<u8>,
+    /// The repository version the content was derived from, if any
+    base: Option<FileBase>,
+}
+
+/// The branch a file was read from and the file's last commit at that time
+#[derive(Clone)]
+struct FileBase {
+    branch: String,
+    last_commit_id: String,
+}
 
-    /// Content changes to apply (format: "old_string:new_string")
-    #[arg(short, long, value_delimiter = ';')]
-    changes: Option<Vec<String>>,
+impl PendingFile {
+    /// The content as text, failing for binary files
+    fn text(&self) -> Result<&str> {
+        std::str::from_utf8(&self.content)
+            .with_context(|| format!("{} is not a UTF-8 text file", self.target_path))
+    }
+}
+
+/// A change to commit to each repository
+enum Change<'a> {
+    /// Create or update files with the given content
+    Write(&'a [PendingFile]),
+    /// Delete the files that exist
+    Delete(&'a [String]),
+    /// Move a file to a new path
+    Move { from: &'a str, to: &'a str },
+}
+
+impl CommitArgs {
+    /// The commit message and author for `project`, with the variables rendered and
+    /// the trailers appended
+    fn details(&self, project: &Project) -> Result<CommitDetails> {
+        let render = |text: &str| {
+            template::render(text, project)
+                .with_context(|| format!("Failed to render commit message {:?}", text))
+        };
+
+        let mut trailers = Vec::new();
+        for (key, value) in &self.trailer {
+            trailers.push((key.clone(), render(value)?));
+        }
+        if let Some(ticket) = &self.ticket {
+            trailers.push(("Refs".to_string(), render(ticket)?));
+        }
+        if let Some(change_id) = &self.change_id {
+            let change_id = match change_id {
+                Some(change_id) => change_id.clone(),
+                None => generated_change_id().to_string(),
+            };
+            trailers.push(("Change-Id".to_string(), change_id));
+        }
+
+        Ok(CommitDetails::new(&render(&self.commit_message)?)
+            .with_author(self.author_name.as_deref(), self.author_email.as_deref())
+            .with_trailers(&trailers))
+    }
+}
+
+impl Change<'_> {
+    /// How many files already have the desired content, given the planned actions
+    fn unchanged(&self, planned: usize) -> usize {
+        match self {
+            Self::Write(files) => files.len() - planned,
+            _ => 0,
+        }
+    }
 }
 
 impl FileCommands {
     pub async fn execute(&self, client: &GitLabClient) -> Result<()> {
         match &self.command {
             FileSubcommands::Update(args) => self.update_files(client, args).await,
+            FileSubcommands::Diff(args) => self.diff_files(client, args).await,
+            FileSubcommands::Edit(args) => self.edit_files(client, args).await,
+            FileSubcommands::SetKey(args) => self.set_key(client, args).await,
+            FileSubcommands::Delete(args) => {
+                self.apply_change(client, &args.commit, &Change::Delete(&args.target_path))
+                    .await
+            }
+            FileSubcommands::Move(args) => {
+                let change = Change::Move {
+                    from: &args.from,
+                    to: &args.to,
+                };
+                self.apply_change(client, &args.commit, &change).await
+            }
+            FileSubcommands::Get(args) => self.get_files(client, args).await,
+            FileSubcommands::Search(args) => self.search_files(client, args).await,
         }
     }
 
     async fn update_files(&self, client: &GitLabClient, args: &UpdateFileArgs) -> Result<()> {
         info!("Updating files in repositories");
 
-        // Get projects from file, command line, or by topic
-        let projects = if let Some(file_path) = &args.project_file {
-            debug!("Loading projects from file: {:?}", file_path);
-            self.load_projects_from_file(file_path)?
-        } else if let Some(project_ids) = &args.project_ids {
-            debug!("Using project IDs from command line: {:?}", project_ids);
-            self.resolve_project_ids(client, project_ids).await?
-        } else if let Some(topic) = &args.topic {
-            debug!("Searching for projects with topic: {}", topic);
-            client.projects().find_by_topic(topic).await?
-        } else {
-            anyhow::bail!("Either --project-file, --project-ids, or --topic must be provided");
-        };
+        let projects = args.commit.selection.resolve(client).await?;
 
         info!("Found {} projects to update", projects.len());
 
-        // Read file content
-        let mut content = std::fs::read_to_string(&args.file_path)
-            .with_context(|| format!("Failed to read file: {:?}", args.file_path))?;
-
-        // Apply content changes if provided
-        if let Some(changes) = &args.changes {
-            for change in changes {
-                let parts: Vec<&str> = change.split(':').collect();
-                if parts.len() == 2 {
-                    let old_str = parts[0];
-                    let new_str = parts[1];
-                    content = content.replace(old_str, new_str);
-                } else {
-                    debug!("Ignoring invalid change format: {}", change);
-                }
-            }
-        }
+        let files = self.load_files(&args.source)?;
+        let guards = args.guards.compile()?;
+
+        info!("Prepared {} files to upload", files.len());
+
+        let merge_request = if args.commit.merge_request.via_mr {
+            Some(self.merge_request_template(client, &args.commit).await?)
+        } else {
+            None
+        };
 
         // Update file in each project
+        let mut results = Vec::new();
+
         for project in &projects {
             info!("Updating file in project: {}", project.path_with_namespace);
 
-            // Get the default branch if none specified
-            let branch = if let Some(branch) = &args.branch {
-                branch.clone()
-            } else {
-                project
-                    .default_branch
-                    .clone()
-                    .unwrap_or_else(|| "main".to_string())
-            };
+            let result = self
+                .update_project(
+                    client,
+                    args,
+                    project,
+                    &files,
+                    &guards,
+                    merge_request.as_ref(),
+                )
+                .await;
 
-            // Check if file exists first
-            let file_exists = client
-                .files()
-                .file_exists(project.id, &args.target_path, &branch)
-                .await?;
+            results.push(
+                result.unwrap_or_else(|e| results::from_error(&project.path_with_namespace, e)),
+            );
+        }
 
-            if file_exists {
-                debug!("File exists, updating: {}", args.target_path);
-                client
-                    .files()
-                    .update_file(
-                        project.id,
-                        &args.target_path,
-                        &branch,
-                        &args.commit_message,
-                        &content,
-                    )
-                    .await
-                    .with_context(|| {
-                        format!(
-                            "Failed to update file {} in project {}",
-                            args.target_path, project.path_with_namespace
-                        )
-                    })?;
-            } else {
-                debug!("File doesn't exist, creating: {}", args.target_path);
-                client
+        results::report(&results)
+    }
+
+    /// Render the files for the project, drop those whose guards fail and commit the rest
+    async fn update_project(
+        &self,
+        client: &GitLabClient,
+        args: &UpdateFileArgs,
+        project: &Project,
+        files: &[PendingFile],
+        guards: &Guards,
+        merge_request: Option<&NewMergeRequest>,
+    ) -> Result<ProjectResult> {
+        let branch = self.read_branch(client, &args.commit, project).await?;
+        let files = self.render_files(&args.source, project, files)?;
+        let fetch = !guards.is_empty() || !args.source.no_normalize;
+
+        let mut refused = Vec::new();
+        let mut allowed = Vec::new();
+
+        for mut file in files {
+            // Fetch each file once, so that the guards, the style match and the conflict
+            // check all see the same version
+            if fetch {
+                let current = client
                     .files()
-                    .create_file(
-                        project.id,
-                        &args.target_path,
-                        &branch,
-                        &args.commit_message,
-                        &content,
-                    )
-                    .await
-                    .with_context(|| {
-                        format!(
-                            "Failed to create file {} in project {}",
-                            args.target_path, project.path_with_namespace
-                        )
-                    })?;
+                    .get_file(project.id, &file.target_path, &branch)
+                    .await?;
+
+                let content = current.as_ref().map(|f| f.content.as_slice());
+                if let Some(reason) = guards.check(&file.target_path, content) {
+                    debug!("Skipping {}: {}", file.target_path, reason);
+                    refused.push(reason);
+                    continue;
+                }
+
+                if let Some(current) = current {
+                    if !args.source.no_normalize {
+                        file.content = text_style::match_style(&current.content, file.content);
+                    }
+                    file.base = Some(FileBase {
+                        branch: branch.clone(),
+                        last_commit_id: current.metadata.last_commit_id,
+                    });
+                }
             }
+            allowed.push(file);
         }
 
-        info!("Successfully updated files in repositories");
-        Ok(())
+        if allowed.is_empty() {
+            return Ok(ProjectResult::skipped(
+                &project.path_with_namespace,
+                format!("{} on {}", refused.join(", "), branch),
+            ));
+        }
+        let files = allowed;
+
+        self.commit_files(
+            client,
+            &args.commit,
+            project,
+            &Change::Write(&files),
+            merge_request,
+        )
+        .await
     }
 
-    fn load_projects_from_file(
+    /// Commit the same change to every selected project
+    async fn apply_change(
         &self,
-        file_path: &PathBuf,
-    ) -> Result<Vec<crate::models::project::Project>> {
-        let extension = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        client: &GitLabClient,
+        args: &CommitArgs,
+        change: &Change<'_>,
+    ) -> Result<()> {
+        let projects = args.selection.resolve(client).await?;
 
-        if extension.to_lowercase() == "csv" {
-            let reader = CsvReader::new(file_path)?;
-            reader.read_projects()
+        info!("Found {} projects to update", projects.len());
+
+        let merge_request = if args.merge_request.via_mr {
+            Some(self.merge_request_template(client, args).await?)
         } else {
+            None
+        };
+
+        let mut results = Vec::new();
+
+        for project in &projects {
+            info!("Updating project: {}", project.path_with_namespace);
+
+            let result = self
+                .commit_files(client, args, project, change, merge_request.as_ref())
+                .await;
+
+            results.push(
+                result.unwrap_or_else(|e| results::from_error(&project.path_with_namespace, e)),
+            );
+        }
+
+        results::report(&results)
+    }
+
+    async fn get_files(&self, client: &GitLabClient, args: &GetFileArgs) -> Result<()> {
+        info!("Downloading {} from repositories", args.target_path);
+
+        let target_path = Path::new(&args.target_path);
+        if target_path
+            .components()
+            .any(|c| !matches!(c, std::path::Component::Normal(_)))
+        {
             anyhow::bail!(
-                "Unsupported file format: {}. Only CSV files are supported.",
-                extension
-            )
+                "Target path must be relative to the repository root: {}",
+                args.target_path
+            );
+        }
+
+        let projects = args.selection.resolve(client).await?;
+
+        info!("Found {} projects to download from", projects.len());
+
+        let mut results = Vec::new();
+        let mut records = Vec::new();
+
+        for project in &projects {
+            let path = &project.path_with_namespace;
+            let branch = match self.target_branch(args.branch.as_deref(), project) {
+                Ok(branch) => branch,
+                Err(e) => {
+                    results.push(ProjectResult::skipped(path, format!("{:#}", e)));
+                    continue;
+                }
+            };
+            let local_path = args.out_dir.join(path).join(target_path);
+
+            let mut record = DownloadRecord {
+                project: path.clone(),
+                branch: branch.clone(),
+                found: false,
+                local_path: String::new(),
+                size: 0,
+                error: String::new(),
+            };
+
+            let download = async {
+                let Some(content) = client
+                    .files()
+                    .get_raw_file(project.id, &args.target_path, &branch)
+                    .await?
+                else {
+                    return Ok(None);
+                };
+
+                if let Some(parent) = local_path.parent() {
+                    std::fs::create_dir_all(parent)
+                        .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+                }
+                std::fs::write(&local_path, &content)
+                    .with_context(|| format!("Failed to write file: {:?}", local_path))?;
+
+                anyhow::Ok(Some(content.len()))
+            };
+
+            let result = match download.await {
+                Ok(Some(size)) => {
+                    record.found = true;
+                    record.local_path = local_path.display().to_string();
+                    record.size = size;
+                    ProjectResult::success(
+                        path,
+                        format!("saved {} bytes to {}", size, local_path.display()),
+                    )
+                }
+                Ok(None) => ProjectResult::skipped(
+                    path,
+                    format!("{} not found on {}", args.target_path, branch),
+                ),
+                Err(e) => {
+                    record.error = format!("{:#}", e);
+                    ProjectResult::failed(path, format!("{:#}", e))
+                }
+            };
+
+            records.push(record);
+            results.push(result);
         }
+
+        std::fs::create_dir_all(&args.out_dir)
+            .with_context(|| format!("Failed to create directory: {:?}", args.out_dir))?;
+        let index = args
+            .index
+            .clone()
+            .unwrap_or_else(|| args.out_dir.join("index.csv"));
+        CsvWriter::new(&index).write_records(&records)?;
+        println!("Wrote index of {} projects to {:?}", records.len(), index);
+
+        results::report(&results)
     }
 
-    async fn resolve_project_ids(
-        &self,
-        client: &GitLabClient,
-        project_ids: &[String],
-    ) -> Result<Vec<crate::models::project::Project>> {
-        let mut projects = Vec::new();
+    async fn search_files(&self, client: &GitLabClient, args: &SearchFileArgs) -> Result<()> {
+        info!("Searching repositories for {:?}", args.pattern);
 
-        for id_or_path in project_ids {
-            // Try to parse as ID first
-            if let Ok(id) = id_or_path.parse::<u64>() {
-                let project = client.projects().get_by_id(id).await?;
-                projects.push(project);
+        let mut hits = Vec::new();
+        let mut failed = Vec::new();
+
+        if args.group.is_some() || args.global {
+            let blobs = match &args.group {
+                Some(group) => {
+                    let group = resolve_group(client, group).await?;
+                    client.search().group_blobs(group.id, &args.pattern).await
+                }
+                None => client.search().global_blobs(&args.pattern).await,
+            }
+            .context("Blob search failed; group and global search need advanced search")?;
+
+            // Results only carry the project ID; a project that cannot be looked up is
+            // reported once and its matches are left out
+            let mut project_paths = HashMap::new();
+            for blob in blobs {
+                if let Entry::Vacant(entry) = project_paths.entry(blob.project_id) {
+                    match client.projects().get_by_id(blob.project_id).await {
+                        Ok(project) => {
+                            entry.insert(Some(project.path_with_namespace));
+                        }
+                        Err(e) => {
+                            failed.push((format!("project {}", blob.project_id), e));
+                            entry.insert(None);
+                        }
+                    }
+                }
+                if let Some(path) = &project_paths[&blob.project_id] {
+                    hits.extend(self.blob_hits(path, &blob, args));
+                }
+            }
+        } else {
+            let projects = args.selection.resolve(client).await?;
+            let pattern = if args.regex {
+                Regex::new(&args.pattern)
             } else {
-                // If not an ID, treat as path
-                let project = client.projects().get_by_path(id_or_path).await?;
-                projects.push(project);
+                Regex::new(&regex::escape(&args.pattern))
             }
+            .with_context(|| format!("Invalid regex: {}", args.pattern))?;
+
+            for project in &projects {
+                match self.search_project(client, project, &pattern, args).await {
+                    Ok(project_hits) => hits.extend(project_hits),
+                    Err(e) => failed.push((project.path_with_namespace.clone(), e)),
+                }
+            }
+        }
+
+        if args.format == "json" {
+            println!("{}", serde_json::to_string_pretty(&hits)?);
+        } else {
+            for hit in &hits {
+                println!(
+                    "{} {}:{} {}",
+                    hit.project.bold(),
+                    hit.path.cyan(),
+                    hit.line,
+                    hit.snippet
+                );
+            }
+            let projects: HashSet<_> = hits.iter().map(|hit| &hit.project).collect();
+            println!("{} matches in {} projects", hits.len(), projects.len());
+        }
+
+        for (project, error) in &failed {
+            eprintln!("{}: {} {:#}", project, "search failed:".red(), error);
+        }
+        if !failed.is_empty() {
+            anyhow::bail!("Search failed in {} projects", failed.len());
         }
 
-        Ok(projects)
+        Ok(())
     }
-}
 
+    /// Search one project, through the blob search or by fetching the given paths
+    async fn search_project(
+        &self,
+        client: &GitLabClient,
+        project: &Project,
+        pattern: &Regex,
+        args: &SearchFileArgs,
+    ) -> Result<Vec<SearchHit>> {
+        let path = &project.path_with_namespace;
+
+        if args.paths.is_empty() {
+            let blobs = client
+                .search()
+                .project_blobs(project.id, &args.pattern, args.branch.as_deref())
+                .await
+                .context("Blob search failed, use --path to search specific files instead")?;
+
+            return Ok(blobs
+                .iter()
+                .flat_map(|blob| self.blob_hits(path, blob, args))
+                .collect());
+        }
+
+        let branch = self.target_branch(args.branch.as_deref(), project)?;
+        let mut hits = Vec::new();
+
+        for file_path in &args.paths {
+            let Some(file) = client
+                .files()
+                .get_file(project.id, file_path, &branch)
+                .await?
+            else {
+                continue;
+            };
+            let Some(content) = file.text() else {
+                debug!("Skipping binary file: {}", file_path);
+                continue;
+            };
+
+            hits.extend(
+                search::matching_lines(content, 1, |line| pattern.is_match(line))
+                    .into_iter()
+                    .map(|(line, snippet)| SearchHit {
+                        project: path.clone(),
+                        path: file_path.clone(),
+                        line,
+                        snippet,
+                    }),
+            );
+        }
+
+        Ok(hits)
+    }
+
+    fn blob_hits(&self, project: &str, blob: &BlobMatch, args: &SearchFileArgs) -> Vec<SearchHit> {
+        blob.matching_lines(&args.pattern)
+            .into_iter()
+            .map(|(line, snippet)| SearchHit {
+                project: project.to_string(),
+                path: blob.path.clone(),
+                line,
+                snippet,
+            })
+            .collect()
+    }
+
+    async fn diff_files(&self, client: &GitLabClient, args: &DiffFileArgs) -> Result<()> {
+        info!("Comparing files with repositories");
+
+        let projects = args.selection.resolve(client).await?;
+        let files = self.load_files(&args.source)?;
+
+        let mut differing = 0;
+        let mut failed = 0;
+
+        for project in &projects {
+            let result = async {
+                let branch = self.target_branch(args.branch.as_deref(), project)?;
+                let files = self.render_files(&args.source, project, &files)?;
+                let files = self
+                    .match_remote_style(client, &args.source, project, &branch, files)
+                    .await?;
+                self.print_diffs(client, project, &branch, &files).await
+            }
+            .await;
+
+            match result {
+                Ok(0) => println!(
+                    "{}: {}",
+                    project.path_with_namespace,
+                    "no differences".green()
+                ),
+                Ok(_) => differing += 1,
+                Err(e) => {
+                    failed += 1;
+                    println!(
+                        "{}: {} {:#}",
+                        project.path_with_namespace,
+                        "failed to compare:".red(),
+                        e
+                    );
+                }
+            }
+        }
+
+        println!(
+            "{} of {} projects differ from the local files",
+            differing,
+            projects.len()
+        );
+
+        if failed > 0 {
+            anyhow::bail!("Failed to compare files in {} projects", failed);
+        }
+
+        Ok(())
+    }
+
+    async fn edit_files(&self, client: &GitLabClient, args: &EditFileArgs) -> Result<()> {
+        info!("Editing {} in repositories", args.target_path);
+
+        let mut edits = args.edits.edits()?;
+        edits.extend(args.remove_line.iter().cloned().map(Edit::RemoveLine));
+        edits.extend(args.append_line.iter().cloned().map(Edit::AppendLine));
+
+        let patch = match &args.patch {
+            Some(path) => Some(
+                std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read patch: {:?}", path))?,
+            ),
+            None => None,
+        };
+
+        if edits.is_empty() && patch.is_none() {
+            anyhow::bail!(
+                "Nothing to edit: provide --replace, --regex, --edits-file, --append-line, --remove-line, or --patch"
+            );
+        }
+
+        let projects = args.commit.selection.resolve(client).await?;
+
+        info!("Found {} projects to edit", projects.len());
+
+        let merge_request = if args.commit.merge_request.via_mr {
+            Some(self.merge_request_template(client, &args.commit).await?)
+        } else {
+            None
+        };
+
+        let mut results = Vec::new();
+
+        for project in &projects {
+            info!("Editing file in project: {}", project.path_with_namespace);
+
+            let result = self
+                .edit_project(
+                    client,
+                    args,
+                    project,
+                    &edits,
+                    patch.as_deref(),
+                    merge_request.as_ref(),
+                )
+                .await;
+
+            results.push(
+                result.unwrap_or_else(|e| results::from_error(&project.path_with_namespace, e)),
+            );
+        }
+
+        results::report(&results)
+    }
+
+    async fn set_key(&self, client: &GitLabClient, args: &SetKeyArgs) -> Result<()> {
+        info!("Setting {} in {}", args.key, args.target_path);
+
+        let format = match args.format.or_else(|| Format::from_path(&args.target_path)) {
+            Some(format) => format,
+            None => anyhow::bail!(
+                "Cannot tell the format of {} from its extension, use --format",
+                args.target_path
+            ),
+        };
+        let key_path = structured::parse_key_path(&args.key)?;
+        let value = structured::parse_value(&args.value, args.string);
+
+        let projects = args.commit.selection.resolve(client).await?;
+
+        info!("Found {} projects to update", projects.len());
+
+        let merge_request = if args.commit.merge_request.via_mr {
+            Some(self.merge_request_template(client, &args.commit).await?)
+        } else {
+            None
+        };
+
+        let mut results = Vec::new();
+
+        for project in &projects {
+            info!("Setting key in project: {}", project.path_with_namespace);
+
+            let result = self
+                .rewrite_file(
+                    client,
+                    &args.commit,
+                    project,
+                    &args.target_path,
+                    merge_request.as_ref(),
+                    |current| {
+                        text_style::preserve(current, !args.no_normalize, |current| {
+                            structured::set_key(current, format, &key_path, &value)
+                                .with_context(|| format!("Failed to set {}", args.key))
+                        })
+                    },
+                )
+                .await;
+
+            results.push(
+                result.unwrap_or_else(|e| results::from_error(&project.path_with_namespace, e)),
+            );
+        }
+
+        results::report(&results)
+    }
+
+    /// Apply the patch and edits to the project's version of the file and commit the result
+    async fn edit_project(
+        &self,
+        client: &GitLabClient,
+        args: &EditFileArgs,
+        project: &Project,
+        edits: &[Edit],
+        patch: Option<&str>,
+        merge_request: Option<&NewMergeRequest>,
+    ) -> Result<ProjectResult> {
+        self.rewrite_file(
+            client,
+            &args.commit,
+            project,
+            &args.target_path,
+            merge_request,
+            |current| {
+                text_style::preserve(current, !args.no_normalize, |current| {
+                    let mut content = match patch {
+                        Some(patch) => diff::apply_patch(current, patch).with_context(|| {
+                            format!("Failed to apply patch to {}", args.target_path)
+                        })?,
+                        None => current.to_string(),
+                    };
+
+                    for edit in edits {
+                        let edit = if args.edits.template {
+                            edit.map_replacement(|text| template::render(text, project))?
+                        } else {
+                            edit.clone()
+                        };
+                        content = edit.apply(&content);
+                    }
+
+                    Ok(content)
+                })
+            },
+        )
+        .await
+    }
+
+    /// Fetch the project's version of `target_path`, transform it and commit the result
+    /// if it changed. Projects without the file are skipped.
+    async fn rewrite_file(
+        &self,
+        client: &GitLabClient,
+        args: &CommitArgs,
+        project: &Project,
+        target_path: &str,
+        merge_request: Option<&NewMergeRequest>,
+        transform: impl FnOnce(&str) -> Result<String>,
+    ) -> Result<ProjectResult> {
+        let path = &project.path_with_namespace;
+        let branch = self.read_branch(client, args, project).await?;
+
+        let Some(current) = client
+            .files()
+            .get_file(project.id, target_path, &branch)
+            .await?
+        else {
+            return Ok(ProjectResult::skipped(
+                path,
+                format!("{} not found on {}", target_path, branch),
+            ));
+        };
+
+        let text = current
+            .text()
+            .with_context(|| format!("{} is not a UTF-8 text file", target_path))?;
+        let content = transform(text)?;
+
+        if content == text {
+            return Ok(ProjectResult::unchanged(
+                path,
+                format!("{} already up to date on {}", target_path, branch),
+            ));
+        }
+
+        let files = [PendingFile {
+            target_path: target_path.to_string(),
+            content: content.into_bytes(),
+            base: Some(FileBase {
+                branch: branch.clone(),
+                last_commit_id: current.metadata.last_commit_id.clone(),
+            }),
+        }];
+
+        self.commit_files(client, args, project, &Change::Write(&files), merge_request)
+            .await
+    }
+
+    /// Print a unified diff for each file that differs from the repository version,
+    /// returning how many files differ
+    async fn print_diffs(
+        &self,
+        client: &GitLabClient,
+        project: &Project,
+        branch: &str,
+        files: &[PendingFile],
+    ) -> Result<usize> {
+        let mut differing = 0;
+
+        for file in files {
+            let current = client
+                .files()
+                .get_file(project.id, &file.target_path, branch)
+                .await?
+                .map(|f| f.content)
+                .unwrap_or_default();
+
+            if current == file.content {
+                continue;
+            }
+            differing += 1;
+
+            let (Ok(old), Ok(new)) = (
+                std::str::from_utf8(&current),
+                std::str::from_utf8(&file.content),
+            ) else {
+                println!(
+                    "Binary file {} differs in {}@{} ({} -> {} bytes)",
+                    file.target_path,
+                    project.path_with_namespace,
+                    branch,
+                    current.len(),
+                    file.content.len()
+                );
+                continue;
+            };
+
+            let diff = diff::unified_diff(
+                old,
+                new,
+                &format!(
+                    "a/{} ({}@{})",
+                    file.target_path, project.path_with_namespace, branch
+                ),
+                &format!("b/{}", file.target_path),
+            );
+            println!("{}", diff::colorize(&diff));
+        }
+
+        Ok(differing)
+    }
+
+    /// Print the planned deletions and moves
+    fn print_actions(&self, project: &Project, branch: &str, actions: &[CommitAction]) {
+        for action in actions {
+            let description = match &action.previous_path {
+                Some(previous_path) => format!("move {} to {}", previous_path, action.file_path),
+                None => format!("{} {}", action.action.as_str(), action.file_path),
+            };
+            println!(
+                "{}@{}: {}",
+                project.path_with_namespace,
+                branch,
+                description.yellow()
+            );
+        }
+    }
+
+    /// Read the local files and apply the content edits. Files are read as bytes,
+    /// so binary files can be uploaded as long as no edits are applied to them.
+    fn load_files(&self, source: &FileSourceArgs) -> Result<Vec<PendingFile>> {
+        let edits = source.edits.edits()?;
+        let mut files = Vec::new();
+
+        for (local_path, target_path) in self.file_mappings(source)? {
+            // Read file content
+            let content = std::fs::read(&local_path)
+                .with_context(|| format!("Failed to read file: {:?}", local_path))?;
+
+            let mut file = PendingFile {
+                target_path,
+                content,
+                base: None,
+            };
+
+            if !edits.is_empty() {
+                file.content = edits::apply_all(&edits, file.text()?).into_bytes();
+            }
+
+            files.push(file);
+        }
+
+        Ok(files)
+    }
+
+    /// Render the template variables of each file for `project` when --template is set
+    fn render_files(
+        &self,
+        source: &FileSourceArgs,
+        project: &Project,
+        files: &[PendingFile],
+    ) -> Result<Vec<PendingFile>> {
+        if !source.edits.template {
+            return Ok(files.to_vec());
+        }
+
+        files
+            .iter()
+            .map(|file| {
+                Ok(PendingFile {
+                    target_path: file.target_path.clone(),
+                    content: template::render(file.text()?, project)
+                        .with_context(|| format!("Failed to render {}", file.target_path))?
+                        .into_bytes(),
+                    base: file.base.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Give each file the line endings, byte order mark and final newline of the
+    /// repository's version, unless --no-normalize is set
+    async fn match_remote_style(
+        &self,
+        client: &GitLabClient,
+        source: &FileSourceArgs,
+        project: &Project,
+        branch: &str,
+        files: Vec<PendingFile>,
+    ) -> Result<Vec<PendingFile>> {
+        if source.no_normalize {
+            return Ok(files);
+        }
+
+        let mut matched = Vec::new();
+        for mut file in files {
+            if let Some(current) = client
+                .files()
+                .get_file(project.id, &file.target_path, branch)
+                .await?
+            {
+                file.content = text_style::match_style(&current.content, file.content);
+            }
+            matched.push(file);
+        }
+
+        Ok(matched)
+    }
+
+    /// Get the branch to update, falling back to the default branch of the repository
+    fn target_branch(&self, branch: Option<&str>, project: &Project) -> Result<String> {
+        if let Some(branch) = branch {
+            return Ok(branch.to_string());
+        }
+
+        project.default_branch.clone().with_context(|| {
+            format!(
+                "{} has no default branch (is the repository empty?); pass --branch",
+                project.path_with_namespace
+            )
+        })
+    }
+
+    /// The branch the current files are read from: the --via-mr feature branch when a
+    /// previous run created it, so that re-runs compare against it, or else the base branch
+    async fn read_branch(
+        &self,
+        client: &GitLabClient,
+        args: &CommitArgs,
+        project: &Project,
+    ) -> Result<String> {
+        let mr_branch = &args.merge_request.mr_branch;
+
+        if args.merge_request.via_mr
+            && client
+                .branches()
+                .get(project.id, mr_branch)
+                .await?
+                .is_some()
+        {
+            return Ok(mr_branch.clone());
+        }
+
+        self.base_branch(client, args, project).await
+    }
+
+    /// The branch holding the current files: the target branch, or the ref it will be
+    /// created from with --create-branch-from
+    async fn base_branch(
+        &self,
+        client: &GitLabClient,
+        args: &CommitArgs,
+        project: &Project,
+    ) -> Result<String> {
+        let branch = self.target_branch(args.branch.as_deref(), project)?;
+
+        if let Some(from) = &args.create_branch_from
+            && client.branches().get(project.id, &branch).await?.is_none()
+        {
+            return Ok(from.clone());
+        }
+
+        Ok(branch)
+    }
+
+    /// Create the target branch from its base unless it already exists
+    async fn ensure_branch(
+        &self,
+        client: &GitLabClient,
+        project: &Project,
+        branch: &str,
+        base: &str,
+    ) -> Result<()> {
+        if branch != base {
+            info!(
+                "Creating branch {} from {} in {}",
+                branch, base, project.path_with_namespace
+            );
+            client
+                .branches()
+                .create(project.id, branch, base)
+                .await
+                .with_context(|| format!("Failed to create branch {} from {}", branch, base))?;
+        }
+        Ok(())
+    }
+
+    /// Collect (local file, repository path) pairs from the file arguments
+    fn file_mappings(&self, args: &FileSourceArgs) -> Result<Vec<(PathBuf, String)>> {
+        if let Some(file_path) = &args.file_path {
+            let target_path = args.target_path.clone().unwrap_or_default();
+            return Ok(vec![(file_path.clone(), target_path)]);
+        }
+
+        if let Some(dir) = &args.dir {
+            let prefix = args.target_path.as_deref().unwrap_or("").trim_matches('/');
+
+            let mut mappings = Vec::new();
+            for local_path in list_files(dir)? {
+                let relative = local_path
+                    .strip_prefix(dir)?
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+
+                let target_path = if prefix.is_empty() {
+                    relative
+                } else {
+                    format!("{}/{}", prefix, relative)
+                };
+                mappings.push((local_path, target_path));
+            }
+
+            if mappings.is_empty() {
+                anyhow::bail!("No files found in directory: {:?}", dir);
+            }
+            return Ok(mappings);
+        }
+
+        if !args.files.is_empty() {
+            return args
+                .files
+                .iter()
+                .map(|pair| match pair.split_once(':') {
+                    Some((src, dest)) if !src.is_empty() && !dest.is_empty() => {
+                        Ok((PathBuf::from(src), dest.to_string()))
+                    }
+                    _ => anyhow::bail!("Invalid --file value {:?}, expected SRC:DEST", pair),
+                })
+                .collect();
+        }
+
+        anyhow::bail!("Either --file-path with --target-path, --file, or --dir must be provided")
+    }
+
+    /// Commit the change directly or through a merge request when a template is given
+    async fn commit_files(
+        &self,
+        client: &GitLabClient,
+        args: &CommitArgs,
+        project: &Project,
+        change: &Change<'_>,
+        merge_request: Option<&NewMergeRequest>,
+    ) -> Result<ProjectResult> {
+        match merge_request {
+            Some(template) => {
+                self.update_via_merge_request(client, args, project, change, template)
+                    .await
+            }
+            None => self.update_on_branch(client, args, project, change).await,
+        }
+    }
+
+    /// Commit the change directly to the target branch
+    async fn update_on_branch(
+        &self,
+        client: &GitLabClient,
+        args: &CommitArgs,
+        project: &Project,
+        change: &Change<'_>,
+    ) -> Result<ProjectResult> {
+        let path = &project.path_with_namespace;
+        let branch = self.target_branch(args.branch.as_deref(), project)?;
+        let base = self.base_branch(client, args, project).await?;
+
+        let actions = self.plan_actions(client, project, &base, change).await?;
+        if actions.is_empty() {
+            return Ok(self.nothing_to_do(path, change, &base));
+        }
+
+        let unchanged = change.unchanged(actions.len());
+
+        if args.dry_run {
+            match change {
+                Change::Write(files) => {
+                    self.print_diffs(client, project, &base, files).await?;
+                }
+                _ => self.print_actions(project, &base, &actions),
+            }
+            let message = if base == branch {
+                format!("would change {} files on {}", actions.len(), branch)
+            } else {
+                format!(
+                    "would create {} from {} and change {} files",
+                    branch,
+                    base,
+                    actions.len()
+                )
+            };
+            return Ok(ProjectResult::dry_run(
+                path,
+                self.with_unchanged(message, unchanged),
+            ));
+        }
+
+        self.ensure_branch(client, project, &branch, &base).await?;
+
+        let summary = self
+            .commit_actions(client, args, project, &branch, actions)
+            .await?;
+
+        Ok(ProjectResult::success(
+            path,
+            self.with_unchanged(format!("{} on {}", summary, branch), unchanged),
+        ))
+    }
+
+    /// Work out the commit actions for the change on `branch`, leaving out files
+    /// that are already as desired
+    async fn plan_actions(
+        &self,
+        client: &GitLabClient,
+        project: &Project,
+        branch: &str,
+        change: &Change<'_>,
+    ) -> Result<Vec<CommitAction>> {
+        let files = match change {
+            Change::Write(files) => files,
+            Change::Delete(paths) => {
+                let mut actions = Vec::new();
+                for target_path in paths.iter() {
+                    if let Some(metadata) = client
+                        .files()
+                        .get_file_metadata(project.id, target_path, branch)
+                        .await?
+                    {
+                        actions.push(
+                            CommitAction::new(CommitActionKind::Delete, target_path)
+                                .with_last_commit_id(&metadata.last_commit_id),
+                        );
+                    } else {
+                        debug!("File not found, skipping: {}", target_path);
+                    }
+                }
+                return Ok(actions);
+            }
+            Change::Move { from, to } => {
+                let files = client.files();
+                let Some(metadata) = files.get_file_metadata(project.id, from, branch).await?
+                else {
+                    return Ok(Vec::new());
+                };
+                if files
+                    .get_file_metadata(project.id, to, branch)
+                    .await?
+                    .is_some()
+                {
+                    anyhow::bail!("Cannot move {} because {} already exists", from, to);
+                }
+                return Ok(vec![
+                    CommitAction::new(CommitActionKind::Move, to)
+                        .with_previous_path(from)
+                        .with_last_commit_id(&metadata.last_commit_id),
+                ]);
+            }
+        };
+
+        let mut actions = Vec::new();
+
+        for file in files.iter() {
+            let metadata = client
+                .files()
+                .get_file_metadata(project.id, &file.target_path, branch)
+                .await?;
+
+            // Content derived from the repository's file must not overwrite newer changes
+            if let Some(base) = &file.base
+                && base.branch == branch
+            {
+                let current = metadata.as_ref().map(|m| m.last_commit_id.as_str());
+                if current != Some(base.last_commit_id.as_str()) {
+                    return Err(FileConflict(format!(
+                        "{} changed on {} since it was read (at {}, now {})",
+                        file.target_path,
+                        branch,
+                        short_sha(&base.last_commit_id),
+                        current.map_or("deleted", short_sha)
+                    ))
+                    .into());
+                }
+            }
+
+            let action = match metadata {
+                Some(metadata) if metadata.matches(&file.content) => {
+                    debug!("File unchanged, skipping: {}", file.target_path);
+                    continue;
+                }
+                Some(metadata) => CommitAction::new(CommitActionKind::Update, &file.target_path)
+                    .with_last_commit_id(&metadata.last_commit_id),
+                None => CommitAction::new(CommitActionKind::Create, &file.target_path),
+            };
+            actions.push(action.with_bytes(&file.content));
+        }
+
+        Ok(actions)
+    }
+
+    /// Apply the actions on `branch`, returning a summary of the changes.
+    /// Several files are committed atomically through the Commits API.
+    async fn commit_actions(
+        &self,
+        client: &GitLabClient,
+        args: &CommitArgs,
+        project: &Project,
+        branch: &str,
+        actions: Vec<CommitAction>,
+    ) -> Result<String> {
+        if let [action] = actions.as_slice()
+            && matches!(
+                action.action,
+                CommitActionKind::Create | CommitActionKind::Update | CommitActionKind::Delete
+            )
+        {
+            return self
+                .commit_single_file(client, args, project, branch, action)
+                .await;
+        }
+
+        let total = actions.len();
+        let summary = [
+            (CommitActionKind::Create, "created"),
+            (CommitActionKind::Update, "updated"),
+            (CommitActionKind::Delete, "deleted"),
+            (CommitActionKind::Move, "moved"),
+        ]
+        .iter()
+        .filter_map(|(kind, label)| {
+            let count = actions.iter().filter(|a| a.action == *kind).count();
+            (count > 0).then(|| format!("{} {}", count, label))
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+        let commit = client
+            .commits()
+            .create(
+                project.id,
+                &NewCommit {
+                    branch: branch.to_string(),
+                    details: args.details(project)?,
+                    actions,
+                },
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to commit {} files in project {}",
+                    total, project.path_with_namespace
+                )
+            })?;
+
+        Ok(format!(
+            "committed {} files ({}) as {}",
+            total, summary, commit.short_id
+        ))
+    }
+
+    /// The result for a project where the change has nothing to do
+    fn nothing_to_do(&self, path: &str, change: &Change<'_>, branch: &str) -> ProjectResult {
+        match change {
+            Change::Write([file]) => ProjectResult::unchanged(
+                path,
+                format!("{} already up to date on {}", file.target_path, branch),
+            ),
+            Change::Write(files) => ProjectResult::unchanged(
+                path,
+                format!("all {} files already up to date on {}", files.len(), branch),
+            ),
+            Change::Delete(paths) => ProjectResult::skipped(
+                path,
+                format!("{} not found on {}", paths.join(", "), branch),
+            ),
+            Change::Move { from, .. } => {
+                ProjectResult::skipped(path, format!("{} not found on {}", from, branch))
+            }
+        }
+    }
+
+    fn with_unchanged(&self, summary: String, unchanged: usize) -> String {
+        if unchanged > 0 {
+            format!("{} ({} unchanged)", summary, unchanged)
+        } else {
+            summary
+        }
+    }
+
+    async fn commit_single_file(
+        &self,
+        client: &GitLabClient,
+        args: &CommitArgs,
+        project: &Project,
+        branch: &str,
+        action: &CommitAction,
+    ) -> Result<String> {
+        let target_path = &action.file_path;
+        let content = &action.content_bytes()?;
+        let commit = &args.details(project)?;
+
+        if action.action == CommitActionKind::Delete {
+            debug!("Deleting file: {}", target_path);
+            client
+                .files()
+                .delete_file(
+                    project.id,
+                    target_path,
+                    branch,
+                    commit,
+                    action.last_commit_id.as_deref(),
+                )
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to delete file {} in project {}",
+                        target_path, project.path_with_namespace
+                    )
+                })?;
+            Ok(format!("deleted {}", target_path))
+        } else if action.action == CommitActionKind::Update {
+            debug!("File exists, updating: {}", target_path);
+            client
+                .files()
+                .update_file(
+                    project.id,
+                    target_path,
+                    branch,
+                    commit,
+                    content,
+                    action.last_commit_id.as_deref(),
+                )
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to update file {} in project {}",
+                        target_path, project.path_with_namespace
+                    )
+                })?;
+            Ok(format!("updated {}", target_path))
+        } else {
+            debug!("File doesn't exist, creating: {}", target_path);
+            client
+                .files()
+                .create_file(project.id, target_path, branch, commit, content)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to create file {} in project {}",
+                        target_path, project.path_with_namespace
+                    )
+                })?;
+            Ok(format!("created {}", target_path))
+        }
+    }
+
+    /// Commit to a feature branch and open a merge request, returning its URL
+    async fn update_via_merge_request(
+        &self,
+        client: &GitLabClient,
+        args: &CommitArgs,
+        project: &Project,
+        change: &Change<'_>,
+        template: &NewMergeRequest,
+    ) -> Result<ProjectResult> {
+        let path = &project.path_with_namespace;
+        let target_branch = self.target_branch(args.branch.as_deref(), project)?;
+        let base = self.base_branch(client, args, project).await?;
+        let source_branch = &args.merge_request.mr_branch;
+
+        let branch_exists = client
+            .branches()
+            .get(project.id, source_branch)
+            .await?
+            .is_some();
+
+        // Compare against the feature branch if a previous run already created it
+        let compare_branch = if branch_exists { source_branch } else { &base };
+
+        let actions = self
+            .plan_actions(client, project, compare_branch, change)
+            .await?;
+
+        let existing = if branch_exists {
+            client
+                .merge_requests()
+                .find_open_from(project.id, source_branch)
+                .await?
+        } else {
+            None
+        };
+
+        if actions.is_empty() {
+            if let Some(existing) = &existing {
+                return Ok(ProjectResult::unchanged(
+                    path,
+                    format!(
+                        "{} already up to date in merge request {}",
+                        source_branch, existing.web_url
+                    ),
+                ));
+            }
+
+            // A previous run may have committed to the branch and failed before opening the
+            // merge request; only open one if the target branch still needs the change
+            if !branch_exists
+                || self
+                    .plan_actions(client, project, &base, change)
+                    .await?
+                    .is_empty()
+            {
+                return Ok(self.nothing_to_do(path, change, compare_branch));
+            }
+        }
+
+        let unchanged = if actions.is_empty() {
+            0
+        } else {
+            change.unchanged(actions.len())
+        };
+
+        if args.dry_run {
+            let changes = if actions.is_empty() {
+                format!("would open a merge request from {}", source_branch)
+            } else {
+                match change {
+                    Change::Write(files) => {
+                        self.print_diffs(client, project, compare_branch, files)
+                            .await?;
+                    }
+                    _ => self.print_actions(project, compare_branch, &actions),
+                }
+                format!("would change {} files", actions.len())
+            };
+            let message = match &existing {
+                Some(existing) => format!("{} in merge request {}", changes, existing.web_url),
+                None if actions.is_empty() => format!("{} into {}", changes, target_branch),
+                None => format!(
+                    "{} and open a merge request from {} into {}",
+                    changes, source_branch, target_branch
+                ),
+            };
+            return Ok(ProjectResult::dry_run(
+                path,
+                self.with_unchanged(message, unchanged),
+            ));
+        }
+
+        self.ensure_branch(client, project, &target_branch, &base)
+            .await?;
+
+        if !branch_exists {
+            client
+                .branches()
+                .create(project.id, source_branch, &target_branch)
+                .await
+                .with_context(|| format!("Failed to create branch {}", source_branch))?;
+        }
+
+        if !actions.is_empty() {
+            self.commit_actions(client, args, project, source_branch, actions)
+                .await?;
+        }
+
+        let (merge_request, summary) = match existing {
+            Some(existing) => {
+                let summary = format!("updated merge request {}", existing.web_url);
+                (existing, summary)
+            }
+            None => {
+                let merge_request = NewMergeRequest {
+                    title: template::render(&template.title, project)
+                        .context("Failed to render the merge request title")?,
+                    source_branch: source_branch.clone(),
+                    target_branch,
+                    ..template.clone()
+                };
+
+                let created = client
+                    .merge_requests()
+                    .create(project.id, &merge_request)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "Failed to create merge request in project {}",
+                            project.path_with_namespace
+                        )
+                    })?;
+                let summary = format!("opened merge request {}", created.web_url);
+                (created, summary)
+            }
+        };
+
+        let mut summary = self.with_unchanged(summary, unchanged);
+
+        // The merge request exists at this point, so a failure here is only a warning; a
+        // re-run retries it. It often fails right after creation while GitLab is still
+        // checking mergeability.
+        if args.merge_request.auto_merge
+            && let Err(e) = client
+                .merge_requests()
+                .merge_when_pipeline_succeeds(project.id, merge_request.iid)
+                .await
+        {
+            warn!(
+                "Failed to enable auto-merge for {}: {:#}",
+                merge_request.web_url, e
+            );
+            summary = format!("{}; warning: failed to enable auto-merge: {:#}", summary, e);
+        }
+
+        Ok(ProjectResult::success(path, summary))
+    }
+
+    /// Build the project-independent part of the merge request
+    async fn merge_request_template(
+        &self,
+        client: &GitLabClient,
+        args: &CommitArgs,
+    ) -> Result<NewMergeRequest> {
+        let mr = &args.merge_request;
+
+        let assignee_ids = self
+            .resolve_user_ids(client, mr.mr_assignees.as_deref())
+            .await?;
+        let reviewer_ids = self
+            .resolve_user_ids(client, mr.mr_reviewers.as_deref())
+            .await?;
+
+        Ok(NewMergeRequest {
+            title: mr
+                .mr_title
+                .clone()
+                .unwrap_or_else(|| args.commit_message.clone()),
+            description: mr.mr_description.clone(),
+            labels: mr.mr_labels.as_ref().map(|labels| labels.join(",")),
+            assignee_ids,
+            reviewer_ids,
+            remove_source_branch: true,
+            ..Default::default()
+        })
+    }
+
+    async fn resolve_user_ids(
+        &self,
+        client: &GitLabClient,
+        usernames: Option<&[String]>,
+    ) -> Result<Vec<u64>> {
+        let mut ids = Vec::new();
+
+        for username in usernames.unwrap_or_default() {
+            let user = client.users().get_by_username(username).await?;
+            ids.push(user.id);
+        }
+
+        Ok(ids)
+    }
+}
+
+/// Recursively list the files below `dir`, skipping `.git` directories
+fn list_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read directory: {:?}", dir))?
+    {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            if path.file_name().is_some_and(|name| name == ".git") {
+                continue;
+            }
+            files.extend(list_files(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Abbreviate a commit SHA the way GitLab displays it
+fn short_sha(sha: &str) -> &str {
+    sha.get(..8).unwrap_or(sha)
+}
+
+/// Parse a KEY=VALUE trailer
+fn parse_trailer(raw: &str) -> Result<(String, String), String> {
+    match raw.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() && !key.contains(char::is_whitespace) => {
+            Ok((key.to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!(
+            "expected KEY=VALUE without spaces in KEY, got {:?}",
+            raw
+        )),
+    }
+}
+
+/// A Gerrit-style Change-Id shared by all commits of this run
+fn generated_change_id() -> &'static str {
+    static CHANGE_ID: OnceLock<String> = OnceLock::new();
+
+    CHANGE_ID.get_or_init(|| {
+        let seed = format!(
+            "{:?}-{}",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default(),
+            std::process::id()
+        );
+        format!("I{}", &content_sha256(seed.as_bytes())[..40])
+    })
+}
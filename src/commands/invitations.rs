@@ -0,0 +1,385 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use tracing::info;
+
+use super::results::{self, ProjectResult};
+use super::selection::ProjectSelectionArgs;
+use crate::gitlab::client::GitLabClient;
+use crate::models::invitation::Invitation;
+use crate::models::user::AccessLevel;
+
+#[derive(Args)]
+pub struct InvitationsCommands {
+    #[command(subcommand)]
+    command: InvitationsSubcommands,
+}
+
+#[derive(Subcommand)]
+enum InvitationsSubcommands {
+    /// List pending invitations
+    List(ListInvitationsArgs),
+
+    /// Resend pending invitations by re-creating them
+    Resend(ResendInvitationsArgs),
+
+    /// Revoke pending invitations
+    Revoke(RevokeInvitationsArgs),
+
+    /// Change the role or expiration of pending invitations
+    Update(UpdateInvitationsArgs),
+}
+
+#[derive(Args)]
+struct ListInvitationsArgs {
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+}
+
+#[derive(Args)]
+struct ResendInvitationsArgs {
+    /// Comma-separated list of invited emails (defaults to all pending invitations)
+    #[arg(long, value_delimiter = ',')]
+    email: Option<Vec<String>>,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Show what would be done without making changes
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Args)]
+struct RevokeInvitationsArgs {
+    /// Comma-separated list of invited emails
+    #[arg(long, value_delimiter = ',', required_unless_present = "all")]
+    email: Option<Vec<String>>,
+
+    /// Revoke every pending invitation
+    #[arg(long, conflicts_with = "email")]
+    all: bool,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Show what would be done without making changes
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Args)]
+struct UpdateInvitationsArgs {
+    /// Comma-separated list of invited emails
+    #[arg(long, value_delimiter = ',', required = true)]
+    email: Vec<String>,
+
+    /// New role/access level for the invitation
+    #[arg(short, long, required_unless_present = "expires_at")]
+    role: Option<AccessLevel>,
+
+    /// New expiration date of the invitation (YYYY-MM-DD)
+    #[arg(long)]
+    expires_at: Option<String>,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Show what would be done without making changes
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl InvitationsCommands {
+    pub async fn execute(&self, client: &GitLabClient) -> Result<()> {
+        match &self.command {
+            InvitationsSubcommands::List(args) => self.list_invitations(client, args).await,
+            InvitationsSubcommands::Resend(args) => self.resend_invitations(client, args).await,
+            InvitationsSubcommands::Revoke(args) => self.revoke_invitations(client, args).await,
+            InvitationsSubcommands::Update(args) => self.update_invitations(client, args).await,
+        }
+    }
+
+    async fn list_invitations(
+        &self,
+        client: &GitLabClient,
+        args: &ListInvitationsArgs,
+    ) -> Result<()> {
+        info!("Listing pending invitations");
+
+        let projects = args.selection.resolve(client).await?;
+
+        println!("Invitations for {} projects:", projects.len());
+        println!("---------------------------");
+
+        for project in &projects {
+            println!(
+                "Project: {} (ID: {})",
+                project.path_with_namespace, project.id
+            );
+
+            match client.invitations().list(project.id).await {
+                Ok(invitations) if invitations.is_empty() => {
+                    println!("  No pending invitations");
+                }
+                Ok(invitations) => {
+                    for invitation in &invitations {
+                        println!(
+                            "  - {} [{}] expires: {}",
+                            invitation.invite_email.green(),
+                            self.role_name(invitation.access_level).yellow(),
+                            invitation.expires_at.as_deref().unwrap_or("never")
+                        );
+                    }
+                }
+                Err(e) => println!("  {} {:#}", "Failed to list invitations:".red(), e),
+            }
+
+            println!("---------------------------");
+        }
+
+        Ok(())
+    }
+
+    async fn resend_invitations(
+        &self,
+        client: &GitLabClient,
+        args: &ResendInvitationsArgs,
+    ) -> Result<()> {
+        info!("Resending pending invitations");
+
+        let projects = args.selection.resolve(client).await?;
+        let mut results = Vec::new();
+
+        for project in &projects {
+            let path = &project.path_with_namespace;
+
+            let invitations = match self
+                .matching_invitations(client, project.id, args.email.as_deref())
+                .await
+            {
+                Ok(invitations) => invitations,
+                Err(e) => {
+                    results.push(ProjectResult::failed(path, format!("{:#}", e)));
+                    continue;
+                }
+            };
+
+            for invitation in invitations {
+                let email = &invitation.invite_email;
+
+                if args.dry_run {
+                    results.push(ProjectResult::dry_run(
+                        path,
+                        format!("would resend invitation to {}", email),
+                    ));
+                    continue;
+                }
+
+                let Some(level) = AccessLevel::from_u64(invitation.access_level) else {
+                    results.push(ProjectResult::skipped(
+                        path,
+                        format!("{} has unknown access level", email),
+                    ));
+                    continue;
+                };
+
+                // GitLab has no resend endpoint; a new invitation sends a fresh email
+                info!("Resending invitation to {} for project {}", email, path);
+
+                if let Err(e) = client.invitations().revoke(project.id, email).await {
+                    results.push(ProjectResult::failed(
+                        path,
+                        format!("failed to resend invitation to {}: {:#}", email, e),
+                    ));
+                    continue;
+                }
+
+                let created = client
+                    .invitations()
+                    .create(
+                        project.id,
+                        email,
+                        level.clone(),
+                        invitation.expires_at.as_deref(),
+                    )
+                    .await;
+
+                results.push(match created {
+                    Ok(()) => {
+                        ProjectResult::success(path, format!("resent invitation to {}", email))
+                    }
+                    // The old invitation is gone; say exactly what to restore by hand
+                    Err(e) => ProjectResult::failed(
+                        path,
+                        format!(
+                            "invitation to {} ({}, expires {}) was revoked but not recreated: {:#}",
+                            email,
+                            level,
+                            invitation.expires_at.as_deref().unwrap_or("never"),
+                            e
+                        ),
+                    ),
+                });
+            }
+        }
+
+        results::report(&results)
+    }
+
+    async fn revoke_invitations(
+        &self,
+        client: &GitLabClient,
+        args: &RevokeInvitationsArgs,
+    ) -> Result<()> {
+        info!("Revoking pending invitations");
+
+        let projects = args.selection.resolve(client).await?;
+        let mut results = Vec::new();
+
+        for project in &projects {
+            let path = &project.path_with_namespace;
+
+            let invitations = match self
+                .matching_invitations(client, project.id, args.email.as_deref())
+                .await
+            {
+                Ok(invitations) => invitations,
+                Err(e) => {
+                    results.push(ProjectResult::failed(path, format!("{:#}", e)));
+                    continue;
+                }
+            };
+
+            if invitations.is_empty() {
+                results.push(ProjectResult::skipped(path, "no matching invitations"));
+                continue;
+            }
+
+            for invitation in invitations {
+                let email = &invitation.invite_email;
+
+                if args.dry_run {
+                    results.push(ProjectResult::dry_run(
+                        path,
+                        format!("would revoke invitation to {}", email),
+                    ));
+                    continue;
+                }
+
+                info!("Revoking invitation to {} for project {}", email, path);
+
+                results.push(match client.invitations().revoke(project.id, email).await {
+                    Ok(()) => {
+                        ProjectResult::success(path, format!("revoked invitation to {}", email))
+                    }
+                    Err(e) => ProjectResult::failed(
+                        path,
+                        format!("failed to revoke invitation to {}: {:#}", email, e),
+                    ),
+                });
+            }
+        }
+
+        results::report(&results)
+    }
+
+    async fn update_invitations(
+        &self,
+        client: &GitLabClient,
+        args: &UpdateInvitationsArgs,
+    ) -> Result<()> {
+        info!("Updating pending invitations");
+
+        let projects = args.selection.resolve(client).await?;
+        let mut results = Vec::new();
+
+        for project in &projects {
+            let path = &project.path_with_namespace;
+
+            let invitations = match self
+                .matching_invitations(client, project.id, Some(&args.email))
+                .await
+            {
+                Ok(invitations) => invitations,
+                Err(e) => {
+                    results.push(ProjectResult::failed(path, format!("{:#}", e)));
+                    continue;
+                }
+            };
+
+            if invitations.is_empty() {
+                results.push(ProjectResult::skipped(path, "no matching invitations"));
+                continue;
+            }
+
+            for invitation in invitations {
+                let email = &invitation.invite_email;
+
+                if args.dry_run {
+                    results.push(ProjectResult::dry_run(
+                        path,
+                        format!("would update invitation to {}", email),
+                    ));
+                    continue;
+                }
+
+                info!("Updating invitation to {} for project {}", email, path);
+
+                let result = client
+                    .invitations()
+                    .update(
+                        project.id,
+                        email,
+                        args.role.clone(),
+                        args.expires_at.as_deref(),
+                    )
+                    .await;
+
+                results.push(match result {
+                    Ok(updated) => ProjectResult::success(
+                        path,
+                        format!(
+                            "updated invitation to {} ({}, expires: {})",
+                            email,
+                            self.role_name(updated.access_level),
+                            updated.expires_at.as_deref().unwrap_or("never")
+                        ),
+                    ),
+                    Err(e) => ProjectResult::failed(
+                        path,
+                        format!("failed to update invitation to {}: {:#}", email, e),
+                    ),
+                });
+            }
+        }
+
+        results::report(&results)
+    }
+
+    /// List the project's invitations, keeping only the given emails when provided
+    async fn matching_invitations(
+        &self,
+        client: &GitLabClient,
+        project_id: u64,
+        emails: Option<&[String]>,
+    ) -> Result<Vec<Invitation>> {
+        let invitations = client.invitations().list(project_id).await?;
+
+        Ok(invitations
+            .into_iter()
+            .filter(|i| match emails {
+                Some(emails) => emails
+                    .iter()
+                    .any(|e| e.eq_ignore_ascii_case(&i.invite_email)),
+                None => true,
+            })
+            .collect())
+    }
+
+    fn role_name(&self, access_level: u64) -> String {
+        AccessLevel::from_u64(access_level)
+            .map(|level| level.to_string())
+            .unwrap_or_else(|| access_level.to_string())
+    }
+}
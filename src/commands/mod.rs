@@ -1,4 +1,9 @@
+pub mod branches;
 pub mod file;
+pub mod invitations;
+pub mod policy;
 pub mod projects;
+pub mod results;
+pub mod selection;
 pub mod topics;
 pub mod user;
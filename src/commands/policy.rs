@@ -0,0 +1,124 @@
+use anyhow::Result;
+use clap::{ArgGroup, Args, Subcommand};
+use tracing::info;
+
+use super::branches::{ProtectionArgs, apply_protection};
+use super::results::{self, ProjectResult};
+use super::selection::ProjectSelectionArgs;
+use crate::gitlab::client::GitLabClient;
+use crate::models::project::Project;
+
+#[derive(Args)]
+pub struct PolicyCommands {
+    #[command(subcommand)]
+    command: PolicySubcommands,
+}
+
+#[derive(Subcommand)]
+enum PolicySubcommands {
+    /// Check that the default branch of each project is protected as required
+    Check(CheckPolicyArgs),
+}
+
+#[derive(Args)]
+#[command(group(
+    ArgGroup::new("policy")
+        .required(true)
+        .multiple(true)
+        .args([
+            "push_access_level",
+            "merge_access_level",
+            "unprotect_access_level",
+            "code_owner_approval",
+            "allow_force_push",
+        ])
+))]
+struct CheckPolicyArgs {
+    #[command(flatten)]
+    protection: ProtectionArgs,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Protect the default branch as required where it differs from the policy
+    #[arg(long)]
+    fix: bool,
+
+    /// With --fix, show what would be fixed without making changes
+    #[arg(long, requires = "fix")]
+    dry_run: bool,
+}
+
+impl PolicyCommands {
+    pub async fn execute(&self, client: &GitLabClient) -> Result<()> {
+        match &self.command {
+            PolicySubcommands::Check(args) => self.check_policy(client, args).await,
+        }
+    }
+
+    async fn check_policy(&self, client: &GitLabClient, args: &CheckPolicyArgs) -> Result<()> {
+        info!("Checking protected branch policy");
+
+        // Validate the policy before fetching any projects
+        args.protection.protection("")?;
+
+        let projects = args.selection.resolve(client).await?;
+
+        info!("Found {} projects to check", projects.len());
+
+        let mut results = Vec::new();
+
+        for project in &projects {
+            let result = self.check_project(client, args, project).await;
+            results.push(result.unwrap_or_else(|e| {
+                ProjectResult::failed(&project.path_with_namespace, format!("{:#}", e))
+            }));
+        }
+
+        results::report(&results)
+    }
+
+    /// Compare the protection of the project's default branch with the policy; projects
+    /// that differ fail the check unless --fix is given
+    async fn check_project(
+        &self,
+        client: &GitLabClient,
+        args: &CheckPolicyArgs,
+        project: &Project,
+    ) -> Result<ProjectResult> {
+        let path = &project.path_with_namespace;
+
+        let Some(branch) = &project.default_branch else {
+            return Ok(ProjectResult::skipped(path, "repository is empty"));
+        };
+        let policy = args.protection.protection(branch)?;
+
+        if args.fix {
+            return apply_protection(client, project, &policy, args.dry_run).await;
+        }
+
+        let Some(current) = client.branches().get_protected(project.id, branch).await? else {
+            return Ok(ProjectResult::failed(
+                path,
+                format!("{} is not protected", branch),
+            ));
+        };
+
+        let differences = policy.differences(&current);
+        if differences.is_empty() {
+            Ok(ProjectResult::unchanged(
+                path,
+                format!("{} matches the policy", branch),
+            ))
+        } else {
+            Ok(ProjectResult::failed(
+                path,
+                format!(
+                    "{} differs from the policy: {}",
+                    branch,
+                    differences.join(", ")
+                ),
+            ))
+        }
+    }
+}
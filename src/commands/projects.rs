@@ -1,9 +1,17 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Args, Subcommand};
 use colored::Colorize;
+use serde_json::{Map, Value};
+use std::io::Write;
 use tracing::{debug, info};
 
+use super::results::{self, ProjectResult};
+use super::selection::{ProjectSelectionArgs, resolve_group};
 use crate::gitlab::client::GitLabClient;
+use crate::models::branch::NewProtectedBranch;
+use crate::models::group::Group;
+use crate::models::project::{Project, parse_setting};
+use crate::models::user::AccessLevel;
 
 #[derive(Args)]
 pub struct ProjectsCommands {
@@ -15,6 +23,30 @@ pub struct ProjectsCommands {
 enum ProjectsSubcommands {
     /// List GitLab projects
     List(ListProjectsArgs),
+
+    /// Share projects with a group
+    Share(ShareProjectsArgs),
+
+    /// Remove a group share from projects
+    Unshare(UnshareProjectsArgs),
+
+    /// Rename the default branch, e.g. from master to main
+    RenameDefaultBranch(RenameDefaultBranchArgs),
+
+    /// Change project settings, e.g. merge_method=ff
+    Set(SetProjectSettingsArgs),
+
+    /// Archive projects, making them read-only
+    Archive(LifecycleArgs),
+
+    /// Unarchive projects
+    Unarchive(LifecycleArgs),
+
+    /// Move projects to another group
+    Transfer(TransferProjectsArgs),
+
+    /// Delete projects
+    Delete(LifecycleArgs),
 }
 
 #[derive(Args)]
@@ -28,10 +60,154 @@ struct ListProjectsArgs {
     format: String,
 }
 
+#[derive(Args)]
+struct ShareProjectsArgs {
+    /// Full path or ID of the group to share projects with
+    #[arg(long)]
+    with_group: String,
+
+    /// Role/access level to grant the group (guest, planner, reporter, developer, maintainer, owner)
+    #[arg(short, long, default_value = "developer")]
+    role: AccessLevel,
+
+    /// Expiration date of the share (YYYY-MM-DD)
+    #[arg(long)]
+    expires_at: Option<String>,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Show what would be done without making changes
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Args)]
+struct UnshareProjectsArgs {
+    /// Full path or ID of the group to remove the share for
+    #[arg(long)]
+    with_group: String,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Show what would be done without making changes
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Args)]
+struct RenameDefaultBranchArgs {
+    /// Current default branch
+    #[arg(long, default_value = "master")]
+    from: String,
+
+    /// New default branch
+    #[arg(long, default_value = "main")]
+    to: String,
+
+    /// Delete the old branch once everything points to the new one
+    #[arg(long)]
+    delete_old: bool,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Show what would be done without making changes
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Args)]
+struct SetProjectSettingsArgs {
+    /// Settings as key=value: merge_method, squash_option,
+    /// only_allow_merge_if_pipeline_succeeds, remove_source_branch_after_merge,
+    /// visibility, description, container_registry_access_level, lfs_enabled
+    #[arg(required = true, value_name = "KEY=VALUE", value_parser = parse_setting_arg)]
+    settings: Vec<(String, Value)>,
+
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Show what would be done without making changes
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// Selection and safety flags for archiving, transferring and deleting projects
+#[derive(Args)]
+struct LifecycleArgs {
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Refuse to continue when more projects than this are selected
+    #[arg(long, default_value_t = 20)]
+    max_targets: usize,
+
+    /// Do not ask for confirmation
+    #[arg(short, long)]
+    yes: bool,
+
+    /// Show what would be done without making changes
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Args)]
+struct TransferProjectsArgs {
+    /// Full path or ID of the group to move the projects to
+    #[arg(long)]
+    to_namespace: String,
+
+    #[command(flatten)]
+    lifecycle: LifecycleArgs,
+}
+
+/// A change to the lifecycle of a project
+enum Lifecycle {
+    Archive,
+    Unarchive,
+    Transfer(Group),
+    Delete,
+}
+
+impl Lifecycle {
+    fn describe(&self) -> String {
+        match self {
+            Self::Archive => "archive".to_string(),
+            Self::Unarchive => "unarchive".to_string(),
+            Self::Transfer(group) => format!("transfer to {}", group.full_path),
+            Self::Delete => "delete".to_string(),
+        }
+    }
+}
+
 impl ProjectsCommands {
     pub async fn execute(&self, client: &GitLabClient) -> Result<()> {
         match &self.command {
             ProjectsSubcommands::List(args) => self.list_projects(client, args).await,
+            ProjectsSubcommands::Share(args) => self.share_projects(client, args).await,
+            ProjectsSubcommands::Unshare(args) => self.unshare_projects(client, args).await,
+            ProjectsSubcommands::RenameDefaultBranch(args) => {
+                self.rename_default_branches(client, args).await
+            }
+            ProjectsSubcommands::Set(args) => self.set_settings(client, args).await,
+            ProjectsSubcommands::Archive(args) => {
+                self.change_lifecycle(client, args, Lifecycle::Archive)
+                    .await
+            }
+            ProjectsSubcommands::Unarchive(args) => {
+                self.change_lifecycle(client, args, Lifecycle::Unarchive)
+                    .await
+            }
+            ProjectsSubcommands::Transfer(args) => {
+                let group = resolve_group(client, &args.to_namespace).await?;
+                self.change_lifecycle(client, &args.lifecycle, Lifecycle::Transfer(group))
+                    .await
+            }
+            ProjectsSubcommands::Delete(args) => {
+                self.change_lifecycle(client, args, Lifecycle::Delete).await
+            }
         }
     }
 
@@ -97,4 +273,484 @@ impl ProjectsCommands {
 
         Ok(())
     }
+
+    async fn share_projects(&self, client: &GitLabClient, args: &ShareProjectsArgs) -> Result<()> {
+        info!("Sharing projects with group {}", args.with_group);
+
+        let group = resolve_group(client, &args.with_group).await?;
+        let projects = args.selection.resolve(client).await?;
+
+        info!("Found {} projects to share", projects.len());
+
+        let mut results = Vec::new();
+
+        for project in &projects {
+            let path = &project.path_with_namespace;
+
+            if args.dry_run {
+                results.push(ProjectResult::dry_run(
+                    path,
+                    format!("would share with {} as {}", group.full_path, args.role),
+                ));
+                continue;
+            }
+
+            info!("Sharing project {} with group {}", path, group.full_path);
+
+            let result = client
+                .projects()
+                .share_with_group(
+                    project.id,
+                    group.id,
+                    args.role.clone(),
+                    args.expires_at.as_deref(),
+                )
+                .await;
+
+            results.push(match result {
+                Ok(()) => ProjectResult::success(
+                    path,
+                    format!("shared with {} as {}", group.full_path, args.role),
+                ),
+                Err(e) => ProjectResult::failed(path, format!("{:#}", e)),
+            });
+        }
+
+        results::report(&results)
+    }
+
+    async fn unshare_projects(
+        &self,
+        client: &GitLabClient,
+        args: &UnshareProjectsArgs,
+    ) -> Result<()> {
+        info!("Removing group {} share from projects", args.with_group);
+
+        let group = resolve_group(client, &args.with_group).await?;
+        let projects = args.selection.resolve(client).await?;
+
+        info!("Found {} projects to unshare", projects.len());
+
+        let mut results = Vec::new();
+
+        for project in &projects {
+            let path = &project.path_with_namespace;
+
+            if args.dry_run {
+                results.push(ProjectResult::dry_run(
+                    path,
+                    format!("would stop sharing with {}", group.full_path),
+                ));
+                continue;
+            }
+
+            info!("Unsharing project {} from group {}", path, group.full_path);
+
+            let result = client
+                .projects()
+                .unshare_with_group(project.id, group.id)
+                .await;
+
+            results.push(match result {
+                Ok(()) => ProjectResult::success(
+                    path,
+                    format!("no longer shared with {}", group.full_path),
+                ),
+                Err(e) => ProjectResult::failed(path, format!("{:#}", e)),
+            });
+        }
+
+        results::report(&results)
+    }
+
+    async fn rename_default_branches(
+        &self,
+        client: &GitLabClient,
+        args: &RenameDefaultBranchArgs,
+    ) -> Result<()> {
+        info!("Renaming default branch {} to {}", args.from, args.to);
+
+        if args.from == args.to {
+            anyhow::bail!("--from and --to must be different branches");
+        }
+
+        let projects = args.selection.resolve(client).await?;
+
+        info!("Found {} projects to update", projects.len());
+
+        let mut results = Vec::new();
+
+        for project in &projects {
+            let result = self.rename_default_branch(client, args, project).await;
+            results.push(result.unwrap_or_else(|e| {
+                ProjectResult::failed(&project.path_with_namespace, format!("{:#}", e))
+            }));
+        }
+
+        results::report(&results)
+    }
+
+    /// Move one project from the old default branch to the new one. Every step is
+    /// skipped when already done, so a failed run can simply be repeated.
+    async fn rename_default_branch(
+        &self,
+        client: &GitLabClient,
+        args: &RenameDefaultBranchArgs,
+        project: &Project,
+    ) -> Result<ProjectResult> {
+        // Projects from a CSV file usually lack the default branch; fetch the current state
+        let project = &client.projects().get_by_id(project.id).await?;
+        let path = &project.path_with_namespace;
+        let branches = client.branches();
+
+        let old_branch = branches.get(project.id, &args.from).await?;
+        let new_branch = branches.get(project.id, &args.to).await?;
+
+        match project.default_branch.as_deref() {
+            Some(default) if default == args.to && old_branch.is_none() => {
+                return Ok(ProjectResult::unchanged(
+                    path,
+                    format!("default branch is already {}", args.to),
+                ));
+            }
+            Some(default) if default == args.from || default == args.to => {}
+            Some(default) => {
+                return Ok(ProjectResult::skipped(
+                    path,
+                    format!("default branch is {}, not {}", default, args.from),
+                ));
+            }
+            None => return Ok(ProjectResult::skipped(path, "repository is empty")),
+        }
+
+        let Some(old_branch) = old_branch else {
+            anyhow::bail!("{} is the default branch but does not exist", args.from);
+        };
+        if let Some(new_branch) = &new_branch
+            && new_branch.commit.id != old_branch.commit.id
+            && project.default_branch.as_deref() == Some(args.from.as_str())
+        {
+            anyhow::bail!(
+                "{} already exists and points to a different commit than {}",
+                args.to,
+                args.from
+            );
+        }
+
+        let old_protection = branches.get_protected(project.id, &args.from).await?;
+        let new_protection = branches.get_protected(project.id, &args.to).await?;
+        let merge_requests = client
+            .merge_requests()
+            .list_open(project.id, &args.from)
+            .await?;
+
+        let mut steps = Vec::new();
+        if new_branch.is_none() {
+            steps.push(format!("create {}", args.to));
+        }
+        let migrate_protection = old_protection.is_some() && new_protection.is_none();
+        if migrate_protection {
+            steps.push(format!("protect {} like {}", args.to, args.from));
+        }
+        if project.default_branch.as_deref() == Some(args.from.as_str()) {
+            steps.push(format!("make {} the default branch", args.to));
+        }
+        if !merge_requests.is_empty() {
+            steps.push(format!("retarget {} merge requests", merge_requests.len()));
+        }
+        if args.delete_old {
+            steps.push(format!("delete {}", args.from));
+        }
+
+        if steps.is_empty() {
+            return Ok(ProjectResult::unchanged(
+                path,
+                format!("default branch is already {}", args.to),
+            ));
+        }
+
+        if args.dry_run {
+            return Ok(ProjectResult::dry_run(
+                path,
+                format!("would {}", steps.join(", ")),
+            ));
+        }
+
+        if new_branch.is_none() {
+            branches
+                .create(project.id, &args.to, &args.from)
+                .await
+                .with_context(|| format!("Failed to create {}", args.to))?;
+        }
+
+        if let Some(old_protection) = &old_protection
+            && migrate_protection
+        {
+            branches
+                .protect(
+                    project.id,
+                    &NewProtectedBranch::copy_of(&args.to, old_protection),
+                )
+                .await
+                .with_context(|| format!("Failed to protect {}", args.to))?;
+        }
+
+        if project.default_branch.as_deref() == Some(args.from.as_str()) {
+            client
+                .projects()
+                .set_default_branch(project.id, &args.to)
+                .await
+                .context("Failed to change the default branch")?;
+        }
+
+        for merge_request in &merge_requests {
+            client
+                .merge_requests()
+                .set_target_branch(project.id, merge_request.iid, &args.to)
+                .await
+                .with_context(|| format!("Failed to retarget {}", merge_request.web_url))?;
+        }
+
+        if args.delete_old {
+            if old_protection.is_some() {
+                branches
+                    .unprotect(project.id, &args.from)
+                    .await
+                    .with_context(|| format!("Failed to unprotect {}", args.from))?;
+            }
+            branches
+                .delete(project.id, &args.from)
+                .await
+                .with_context(|| format!("Failed to delete {}", args.from))?;
+        }
+
+        Ok(ProjectResult::success(
+            path,
+            format!("moved {} to {}: {}", args.from, args.to, steps.join(", ")),
+        ))
+    }
+
+    async fn set_settings(
+        &self,
+        client: &GitLabClient,
+        args: &SetProjectSettingsArgs,
+    ) -> Result<()> {
+        info!("Changing project settings");
+
+        let projects = args.selection.resolve(client).await?;
+
+        info!("Found {} projects to update", projects.len());
+
+        let mut results = Vec::new();
+
+        for project in &projects {
+            let result = self.set_project_settings(client, args, project).await;
+            results.push(result.unwrap_or_else(|e| {
+                ProjectResult::failed(&project.path_with_namespace, format!("{:#}", e))
+            }));
+        }
+
+        results::report(&results)
+    }
+
+    /// Change the settings that differ, reporting each one's old and new value
+    async fn set_project_settings(
+        &self,
+        client: &GitLabClient,
+        args: &SetProjectSettingsArgs,
+        project: &Project,
+    ) -> Result<ProjectResult> {
+        let path = &project.path_with_namespace;
+        let before = client.projects().get_settings(project.id).await?;
+
+        let changes: Map<String, Value> = args
+            .settings
+            .iter()
+            .filter(|(key, value)| before.get(key) != Some(value))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+
+        if changes.is_empty() {
+            return Ok(ProjectResult::unchanged(
+                path,
+                "settings already as requested",
+            ));
+        }
+
+        let describe = |after: &Value| {
+            changes
+                .keys()
+                .map(|key| {
+                    format!(
+                        "{}: {} -> {}",
+                        key,
+                        before.get(key).unwrap_or(&Value::Null),
+                        after.get(key).unwrap_or(&Value::Null)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        if args.dry_run {
+            return Ok(ProjectResult::dry_run(
+                path,
+                format!("would set {}", describe(&Value::Object(changes.clone()))),
+            ));
+        }
+
+        let after = client
+            .projects()
+            .update_settings(project.id, &changes)
+            .await?;
+
+        // GitLab ignores settings that are unavailable or not permitted instead of failing
+        let ignored: Vec<&String> = changes
+            .iter()
+            .filter(|(key, value)| after.get(key.as_str()) != Some(value))
+            .map(|(key, _)| key)
+            .collect();
+        if !ignored.is_empty() {
+            return Ok(ProjectResult::failed(
+                path,
+                format!(
+                    "GitLab did not apply {}; now {}",
+                    ignored
+                        .iter()
+                        .map(|k| k.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    describe(&after)
+                ),
+            ));
+        }
+
+        Ok(ProjectResult::success(
+            path,
+            format!("set {}", describe(&after)),
+        ))
+    }
+
+    /// Archive, unarchive, transfer or delete the selected projects after the safety checks
+    async fn change_lifecycle(
+        &self,
+        client: &GitLabClient,
+        args: &LifecycleArgs,
+        lifecycle: Lifecycle,
+    ) -> Result<()> {
+        let action = lifecycle.describe();
+        info!("Preparing to {} projects", action);
+
+        let projects = args.selection.resolve(client).await?;
+
+        if projects.is_empty() {
+            println!("No projects selected.");
+            return Ok(());
+        }
+
+        if projects.len() > args.max_targets {
+            anyhow::bail!(
+                "Refusing to {} {} projects, more than --max-targets {}; check the selection or raise the limit",
+                action,
+                projects.len(),
+                args.max_targets
+            );
+        }
+
+        if !args.dry_run && !args.yes && !confirm(&action, &projects)? {
+            println!("Aborted, no projects were changed.");
+            return Ok(());
+        }
+
+        let mut results = Vec::new();
+
+        for project in &projects {
+            let result = self
+                .change_project_lifecycle(client, project, &lifecycle, args.dry_run)
+                .await;
+            results.push(result.unwrap_or_else(|e| {
+                ProjectResult::failed(&project.path_with_namespace, format!("{:#}", e))
+            }));
+        }
+
+        results::report(&results)
+    }
+
+    async fn change_project_lifecycle(
+        &self,
+        client: &GitLabClient,
+        project: &Project,
+        lifecycle: &Lifecycle,
+        dry_run: bool,
+    ) -> Result<ProjectResult> {
+        let path = &project.path_with_namespace;
+        let projects = client.projects();
+
+        // Skip projects that are already in the requested state
+        let unchanged = match lifecycle {
+            Lifecycle::Archive | Lifecycle::Unarchive => {
+                let archived = projects.get_settings(project.id).await?["archived"] == true;
+                match (lifecycle, archived) {
+                    (Lifecycle::Archive, true) => Some("already archived"),
+                    (Lifecycle::Unarchive, false) => Some("not archived"),
+                    _ => None,
+                }
+            }
+            Lifecycle::Transfer(group) => path
+                .rsplit_once('/')
+                .is_some_and(|(namespace, _)| namespace == group.full_path)
+                .then_some("already in the group"),
+            Lifecycle::Delete => None,
+        };
+        if let Some(reason) = unchanged {
+            return Ok(ProjectResult::unchanged(path, reason));
+        }
+
+        if dry_run {
+            return Ok(ProjectResult::dry_run(
+                path,
+                format!("would {}", lifecycle.describe()),
+            ));
+        }
+
+        let message = match lifecycle {
+            Lifecycle::Archive => {
+                projects.archive(project.id).await?;
+                "archived".to_string()
+            }
+            Lifecycle::Unarchive => {
+                projects.unarchive(project.id).await?;
+                "unarchived".to_string()
+            }
+            Lifecycle::Transfer(group) => {
+                let moved = projects.transfer(project.id, group.id).await?;
+                format!("moved to {}", moved.path_with_namespace)
+            }
+            Lifecycle::Delete => {
+                projects.delete(project.id).await?;
+                "deleted (or scheduled for deletion)".to_string()
+            }
+        };
+
+        Ok(ProjectResult::success(path, message))
+    }
+}
+
+/// List the projects and ask on the terminal whether to go ahead
+fn confirm(action: &str, projects: &[Project]) -> Result<bool> {
+    println!("About to {} {} projects:", action.bold(), projects.len());
+    for project in projects {
+        println!("  - {}", project.path_with_namespace);
+    }
+    print!("Continue? [y/N] ");
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+fn parse_setting_arg(raw: &str) -> Result<(String, Value), String> {
+    parse_setting(raw).map_err(|e| e.to_string())
 }
@@ -0,0 +1,128 @@
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::models::file::FileConflict;
+
+/// Outcome of a bulk operation on a single project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Success,
+    Unchanged,
+    Skipped,
+    DryRun,
+    Conflict,
+    Failed,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "ok",
+            Self::Unchanged => "unchanged",
+            Self::Skipped => "skipped",
+            Self::DryRun => "dry-run",
+            Self::Conflict => "conflict",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProjectResult {
+    pub project: String,
+    pub status: Status,
+    pub message: String,
+}
+
+impl ProjectResult {
+    pub fn new(project: &str, status: Status, message: impl Into<String>) -> Self {
+        Self {
+            project: project.to_string(),
+            status,
+            message: message.into(),
+        }
+    }
+
+    pub fn success(project: &str, message: impl Into<String>) -> Self {
+        Self::new(project, Status::Success, message)
+    }
+
+    pub fn unchanged(project: &str, message: impl Into<String>) -> Self {
+        Self::new(project, Status::Unchanged, message)
+    }
+
+    pub fn skipped(project: &str, message: impl Into<String>) -> Self {
+        Self::new(project, Status::Skipped, message)
+    }
+
+    pub fn dry_run(project: &str, message: impl Into<String>) -> Self {
+        Self::new(project, Status::DryRun, message)
+    }
+
+    pub fn conflict(project: &str, message: impl Into<String>) -> Self {
+        Self::new(project, Status::Conflict, message)
+    }
+
+    pub fn failed(project: &str, message: impl Into<String>) -> Self {
+        Self::new(project, Status::Failed, message)
+    }
+}
+
+/// The result for a project whose operation returned an error; a `FileConflict`
+/// anywhere in the chain is reported as a conflict rather than a failure
+pub fn from_error(project: &str, error: anyhow::Error) -> ProjectResult {
+    if error.chain().any(|e| e.is::<FileConflict>()) {
+        ProjectResult::conflict(project, format!("{:#}", error))
+    } else {
+        ProjectResult::failed(project, format!("{:#}", error))
+    }
+}
+
+/// Print a per-project summary and fail if any project failed
+pub fn report(results: &[ProjectResult]) -> Result<()> {
+    println!("Results for {} projects:", results.len());
+
+    for result in results {
+        let label = result.status.as_str();
+        let label = match result.status {
+            Status::Success => label.green(),
+            Status::Unchanged => label.normal(),
+            Status::Skipped => label.yellow(),
+            Status::DryRun => label.cyan(),
+            Status::Conflict => label.magenta(),
+            Status::Failed => label.red(),
+        };
+
+        println!("  [{}] {}: {}", label, result.project, result.message);
+    }
+
+    let count = |status: Status| results.iter().filter(|r| r.status == status).count();
+    let failed = count(Status::Failed);
+    let conflicts = count(Status::Conflict);
+
+    println!(
+        "{} succeeded, {} unchanged, {} skipped, {} dry-run, {} conflicts, {} failed",
+        count(Status::Success),
+        count(Status::Unchanged),
+        count(Status::Skipped),
+        count(Status::DryRun),
+        conflicts,
+        failed
+    );
+
+    if conflicts > 0 {
+        println!(
+            "Files with conflicts changed while they were being updated; run the command again."
+        );
+    }
+
+    if failed + conflicts > 0 {
+        anyhow::bail!(
+            "{} of {} projects failed or had conflicts",
+            failed + conflicts,
+            results.len()
+        );
+    }
+
+    Ok(())
+}
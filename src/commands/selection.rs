@@ -0,0 +1,95 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use std::path::PathBuf;
+use tracing::debug;
+
+use crate::gitlab::client::GitLabClient;
+use crate::models::group::Group;
+use crate::models::project::Project;
+use crate::utils::csv::CsvReader;
+
+/// Project selection flags shared by commands that operate on many projects
+#[derive(Args)]
+pub struct ProjectSelectionArgs {
+    /// Path to CSV file containing project details
+    #[arg(long, conflicts_with = "project_ids")]
+    pub project_file: Option<PathBuf>,
+
+    /// Comma-separated list of project IDs or paths
+    #[arg(long, value_delimiter = ',', conflicts_with = "project_file")]
+    pub project_ids: Option<Vec<String>>,
+
+    /// GitLab topic to filter projects
+    #[arg(long)]
+    pub topic: Option<String>,
+}
+
+impl ProjectSelectionArgs {
+    /// Whether any of the selection flags was given
+    pub fn is_provided(&self) -> bool {
+        self.project_file.is_some() || self.project_ids.is_some() || self.topic.is_some()
+    }
+
+    pub async fn resolve(&self, client: &GitLabClient) -> Result<Vec<Project>> {
+        // Get projects from file, command line, or by topic
+        if let Some(file_path) = &self.project_file {
+            debug!("Loading projects from file: {:?}", file_path);
+            load_projects_from_file(file_path)
+        } else if let Some(project_ids) = &self.project_ids {
+            debug!("Using project IDs from command line: {:?}", project_ids);
+            resolve_project_ids(client, project_ids).await
+        } else if let Some(topic) = &self.topic {
+            debug!("Searching for projects with topic: {}", topic);
+            client.projects().find_by_topic(topic).await
+        } else {
+            anyhow::bail!("Either --project-file, --project-ids, or --topic must be provided");
+        }
+    }
+}
+
+pub fn load_projects_from_file(file_path: &PathBuf) -> Result<Vec<Project>> {
+    let extension = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+    if extension.to_lowercase() == "csv" {
+        let reader = CsvReader::new(file_path)?;
+        reader.read_projects()
+    } else {
+        anyhow::bail!(
+            "Unsupported file format: {}. Only CSV files are supported.",
+            extension
+        )
+    }
+}
+
+pub async fn resolve_project_ids(
+    client: &GitLabClient,
+    project_ids: &[String],
+) -> Result<Vec<Project>> {
+    let mut projects = Vec::new();
+
+    for id_or_path in project_ids {
+        // Try to parse as ID first
+        if let Ok(id) = id_or_path.parse::<u64>() {
+            let project = client.projects().get_by_id(id).await?;
+            projects.push(project);
+        } else {
+            // If not an ID, treat as path
+            let project = client.projects().get_by_path(id_or_path).await?;
+            projects.push(project);
+        }
+    }
+
+    Ok(projects)
+}
+
+/// Find a group by its numeric ID or full path
+pub async fn resolve_group(client: &GitLabClient, id_or_path: &str) -> Result<Group> {
+    // Try to parse as ID first
+    let group = if let Ok(id) = id_or_path.parse::<u64>() {
+        client.groups().get_by_id(id).await
+    } else {
+        client.groups().get_by_path(id_or_path).await
+    };
+
+    group.with_context(|| format!("Failed to find group {}", id_or_path))
+}
@@ -1,11 +1,15 @@
 use anyhow::{Context, Result};
 use clap::{Args, Subcommand};
+use serde::Serialize;
 use std::path::PathBuf;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
+use super::results::{self, ProjectResult};
+use super::selection::ProjectSelectionArgs;
 use crate::gitlab::client::GitLabClient;
-use crate::models::user::AccessLevel;
-use crate::utils::csv::CsvReader;
+use crate::models::member::{self, AddedAs, Membership};
+use crate::models::user::{AccessLevel, User};
+use crate::utils::csv::{CsvReader, CsvWriter};
 
 #[derive(Args)]
 pub struct UserCommands {
@@ -20,6 +24,15 @@ enum UserSubcommands {
 
     /// Remove users from projects
     Remove(RemoveUserArgs),
+
+    /// Remove a user from every project and group they belong to
+    Offboard(OffboardUserArgs),
+
+    /// Copy the members of a template project to other projects
+    Copy(CopyMembersArgs),
+
+    /// Give a user the same project and group access as another user
+    CloneAccess(CloneAccessArgs),
 }
 
 #[derive(Args)]
@@ -70,6 +83,89 @@ struct RemoveUserArgs {
     /// GitLab topic to filter projects
     #[arg(short, long)]
     topic: Option<String>,
+
+    /// Remove users even if a project would be left without an Owner or Maintainer
+    #[arg(long)]
+    force: bool,
+}
+
+#[derive(Args)]
+struct OffboardUserArgs {
+    /// User ID or username of the user to offboard
+    user: String,
+
+    /// User ID or username of a successor who takes over Maintainer/Owner roles
+    #[arg(long)]
+    successor: Option<String>,
+
+    /// Path to the audit CSV file describing what was removed
+    #[arg(long, default_value = "offboard-audit.csv")]
+    audit_file: PathBuf,
+
+    /// Remove the user even if a project would be left without an Owner or Maintainer
+    #[arg(long)]
+    force: bool,
+
+    /// Projects to scan for memberships (defaults to all memberships, requires admin)
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Show what would be done without making changes
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Args)]
+struct CopyMembersArgs {
+    /// ID or path of the project whose members are copied
+    #[arg(long)]
+    from_project: String,
+
+    /// Highest role to grant; members with higher roles are capped to this one
+    #[arg(long)]
+    max_role: Option<AccessLevel>,
+
+    /// Projects to copy the members to
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Show what would be done without making changes
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Args)]
+struct CloneAccessArgs {
+    /// User ID or username of the user whose access is copied
+    #[arg(long)]
+    from_user: String,
+
+    /// User ID or username of the user who receives the access
+    #[arg(long)]
+    to_user: String,
+
+    /// Highest role to grant; higher roles are capped to this one
+    #[arg(long)]
+    max_role: Option<AccessLevel>,
+
+    /// Projects to scan for memberships (defaults to all memberships, requires admin)
+    #[command(flatten)]
+    selection: ProjectSelectionArgs,
+
+    /// Show what would be done without making changes
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct OffboardRecord {
+    source_type: String,
+    source_id: u64,
+    source_name: String,
+    access_level: u64,
+    successor: String,
+    status: String,
+    message: String,
 }
 
 impl UserCommands {
@@ -77,6 +173,9 @@ impl UserCommands {
         match &self.command {
             UserSubcommands::Add(args) => self.add_users(client, args).await,
             UserSubcommands::Remove(args) => self.remove_users(client, args).await,
+            UserSubcommands::Offboard(args) => self.offboard_user(client, args).await,
+            UserSubcommands::Copy(args) => self.copy_members(client, args).await,
+            UserSubcommands::CloneAccess(args) => self.clone_access(client, args).await,
         }
     }
 
@@ -113,27 +212,46 @@ impl UserCommands {
         info!("Found {} projects to modify", projects.len());
 
         // Add users to projects
+        let mut results = Vec::new();
+        let mut direct = 0;
+        let mut invited = 0;
+
         for user in users {
             for project in &projects {
                 info!(
                     "Adding user {} to project {}",
                     user.username, project.path_with_namespace
                 );
-                client
+                let result = client
                     .users()
                     .add_to_project(user.id, project.id, args.role.clone())
-                    .await
-                    .with_context(|| {
-                        format!(
-                            "Failed to add user {} to project {}",
-                            user.username, project.path_with_namespace
+                    .await;
+
+                results.push(match result {
+                    Ok(added_as) => {
+                        match added_as {
+                            AddedAs::Member => direct += 1,
+                            AddedAs::Invitation => invited += 1,
+                        }
+                        ProjectResult::success(
+                            &project.path_with_namespace,
+                            self.added_message(&user.username, &args.role, &added_as),
                         )
-                    })?;
+                    }
+                    Err(e) => ProjectResult::failed(
+                        &project.path_with_namespace,
+                        format!("failed to add {}: {:#}", user.username, e),
+                    ),
+                });
             }
         }
 
-        info!("Successfully added users to projects");
-        Ok(())
+        println!(
+            "{} added as direct members, {} pending invitations",
+            direct, invited
+        );
+
+        results::report(&results)
     }
 
     async fn remove_users(&self, client: &GitLabClient, args: &RemoveUserArgs) -> Result<()> {
@@ -169,8 +287,42 @@ impl UserCommands {
         info!("Found {} projects to modify", projects.len());
 
         // Remove users from projects
+        let mut refused = Vec::new();
+        let mut unchecked = Vec::new();
+
         for user in users {
             for project in &projects {
+                let orphans = if args.force {
+                    false
+                } else {
+                    match self.would_orphan_project(client, &user, project.id).await {
+                        Ok(orphans) => orphans,
+                        Err(e) => {
+                            warn!(
+                                "Not removing user {} from project {}: {:#}",
+                                user.username, project.path_with_namespace, e
+                            );
+                            unchecked.push(format!(
+                                "{} ({}): {:#}",
+                                project.path_with_namespace, user.username, e
+                            ));
+                            continue;
+                        }
+                    }
+                };
+
+                if orphans {
+                    warn!(
+                        "Not removing user {} from project {}: no Owner or Maintainer would remain",
+                        user.username, project.path_with_namespace
+                    );
+                    refused.push(format!(
+                        "{} ({})",
+                        project.path_with_namespace, user.username
+                    ));
+                    continue;
+                }
+
                 info!(
                     "Removing user {} from project {}",
                     user.username, project.path_with_namespace
@@ -188,10 +340,553 @@ impl UserCommands {
             }
         }
 
+        if !refused.is_empty() {
+            println!("Refused removals that would leave projects without an Owner or Maintainer:");
+            for project in &refused {
+                println!("  - {}", project);
+            }
+        }
+        if !unchecked.is_empty() {
+            println!("Skipped removals whose remaining maintainers could not be checked:");
+            for project in &unchecked {
+                println!("  - {}", project);
+            }
+        }
+
+        if !refused.is_empty() {
+            anyhow::bail!(
+                "Refused to remove the last Owner/Maintainer from {} projects; use --force to remove anyway",
+                refused.len()
+            );
+        }
+        if !unchecked.is_empty() {
+            anyhow::bail!(
+                "Could not check the remaining maintainers of {} projects",
+                unchecked.len()
+            );
+        }
+
         info!("Successfully removed users from projects");
         Ok(())
     }
 
+    async fn offboard_user(&self, client: &GitLabClient, args: &OffboardUserArgs) -> Result<()> {
+        info!("Offboarding user {}", args.user);
+
+        let user = self.resolve_user(client, &args.user).await?;
+        let successor = match &args.successor {
+            Some(successor) => Some(self.resolve_user(client, successor).await?),
+            None => None,
+        };
+
+        let memberships = if args.selection.is_provided() {
+            self.scan_project_memberships(client, &user, &args.selection)
+                .await?
+        } else {
+            client
+                .users()
+                .get_memberships(user.id)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to list memberships of user {}. Listing memberships requires \
+                         an admin token; select projects to scan instead",
+                        user.username
+                    )
+                })?
+        };
+
+        info!(
+            "Found {} memberships for user {}",
+            memberships.len(),
+            user.username
+        );
+
+        let mut results = Vec::new();
+        let mut records = Vec::new();
+
+        for membership in &memberships {
+            let result = self
+                .offboard_membership(client, &user, successor.as_ref(), membership, args)
+                .await;
+
+            records.push(OffboardRecord {
+                source_type: membership.source_type.clone(),
+                source_id: membership.source_id,
+                source_name: membership.source_name.clone(),
+                access_level: membership.access_level,
+                successor: successor
+                    .as_ref()
+                    .map(|s| s.username.clone())
+                    .unwrap_or_default(),
+                status: result.status.as_str().to_string(),
+                message: result.message.clone(),
+            });
+            results.push(result);
+        }
+
+        CsvWriter::new(&args.audit_file).write_records(&records)?;
+        info!("Wrote audit log to {:?}", args.audit_file);
+
+        results::report(&results)
+    }
+
+    async fn offboard_membership(
+        &self,
+        client: &GitLabClient,
+        user: &User,
+        successor: Option<&User>,
+        membership: &Membership,
+        args: &OffboardUserArgs,
+    ) -> ProjectResult {
+        let name = &membership.source_name;
+        let level = AccessLevel::from_u64(membership.access_level);
+
+        // Maintainer and Owner roles are handed over before the user is removed
+        let handover = match (successor, &level) {
+            (Some(successor), Some(level @ (AccessLevel::Maintainer | AccessLevel::Owner))) => {
+                Some((successor, level.clone()))
+            }
+            _ => None,
+        };
+
+        if membership.is_project() && handover.is_none() && !args.force {
+            match self
+                .would_orphan_project(client, user, membership.source_id)
+                .await
+            {
+                Ok(true) => {
+                    return ProjectResult::failed(
+                        name,
+                        "refused: no Owner or Maintainer would remain (use --force)",
+                    );
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    return ProjectResult::failed(
+                        name,
+                        format!("failed to check remaining maintainers: {:#}", e),
+                    );
+                }
+            }
+        }
+
+        if args.dry_run {
+            let message = match &handover {
+                Some((successor, level)) => format!(
+                    "would grant {} {} and remove {}",
+                    successor.username, level, user.username
+                ),
+                None => format!("would remove {}", user.username),
+            };
+            return ProjectResult::dry_run(name, message);
+        }
+
+        let mut granted = None;
+        if let Some((successor, level)) = &handover {
+            match self.hand_over(client, successor, membership, level).await {
+                Ok(message) => granted = Some(message),
+                Err(e) => {
+                    warn!(
+                        "Not removing {} from {}: handover failed",
+                        user.username, name
+                    );
+                    return ProjectResult::failed(
+                        name,
+                        format!("failed to grant {} access: {:#}", successor.username, e),
+                    );
+                }
+            }
+        }
+
+        info!("Removing user {} from {}", user.username, name);
+
+        let removed = if membership.is_project() {
+            client
+                .users()
+                .remove_from_project(user.id, membership.source_id)
+                .await
+        } else {
+            client
+                .users()
+                .remove_from_group(user.id, membership.source_id)
+                .await
+        };
+
+        match (removed, granted) {
+            (Ok(()), Some(granted)) => {
+                ProjectResult::success(name, format!("removed {}, {}", user.username, granted))
+            }
+            (Ok(()), None) => ProjectResult::success(name, format!("removed {}", user.username)),
+            (Err(e), _) => ProjectResult::failed(name, format!("{:#}", e)),
+        }
+    }
+
+    async fn copy_members(&self, client: &GitLabClient, args: &CopyMembersArgs) -> Result<()> {
+        info!("Copying members from project {}", args.from_project);
+
+        let template = self
+            .resolve_project_ids(client, std::slice::from_ref(&args.from_project))
+            .await?
+            .remove(0);
+
+        let members: Vec<_> = client
+            .users()
+            .list_project_members(template.id)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to list members of project {}",
+                    template.path_with_namespace
+                )
+            })?
+            .into_iter()
+            .filter(|m| m.state == "active")
+            .collect();
+
+        info!(
+            "Found {} active members in project {}",
+            members.len(),
+            template.path_with_namespace
+        );
+
+        let projects = args.selection.resolve(client).await?;
+
+        info!("Found {} projects to modify", projects.len());
+
+        let mut results = Vec::new();
+
+        for project in projects.iter().filter(|p| p.id != template.id) {
+            let path = &project.path_with_namespace;
+
+            let existing = match client.users().list_project_members(project.id).await {
+                Ok(existing) => existing,
+                Err(e) => {
+                    results.push(ProjectResult::failed(
+                        path,
+                        format!("failed to list members: {:#}", e),
+                    ));
+                    continue;
+                }
+            };
+
+            for member in &members {
+                if existing.iter().any(|e| e.id == member.id) {
+                    results.push(ProjectResult::skipped(
+                        path,
+                        format!("{} is already a member", member.username),
+                    ));
+                    continue;
+                }
+
+                let Some(level) = self.capped_role(member.access_level, args.max_role.as_ref())
+                else {
+                    results.push(ProjectResult::skipped(
+                        path,
+                        format!(
+                            "{} has unknown access level {}",
+                            member.username, member.access_level
+                        ),
+                    ));
+                    continue;
+                };
+
+                if args.dry_run {
+                    results.push(ProjectResult::dry_run(
+                        path,
+                        format!("would add {} as {}", member.username, level),
+                    ));
+                    continue;
+                }
+
+                info!("Adding user {} to project {}", member.username, path);
+
+                let result = client
+                    .users()
+                    .add_to_project(member.id, project.id, level.clone())
+                    .await;
+
+                results.push(match result {
+                    Ok(added_as) => ProjectResult::success(
+                        path,
+                        self.added_message(&member.username, &level, &added_as),
+                    ),
+                    Err(e) => ProjectResult::failed(
+                        path,
+                        format!("failed to add {}: {:#}", member.username, e),
+                    ),
+                });
+            }
+        }
+
+        results::report(&results)
+    }
+
+    async fn clone_access(&self, client: &GitLabClient, args: &CloneAccessArgs) -> Result<()> {
+        info!(
+            "Cloning access of user {} to user {}",
+            args.from_user, args.to_user
+        );
+
+        let from_user = self.resolve_user(client, &args.from_user).await?;
+        let to_user = self.resolve_user(client, &args.to_user).await?;
+
+        let memberships = if args.selection.is_provided() {
+            self.scan_project_memberships(client, &from_user, &args.selection)
+                .await?
+        } else {
+            client
+                .users()
+                .get_memberships(from_user.id)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to list memberships of user {}. Listing memberships requires \
+                         an admin token; select projects to scan instead",
+                        from_user.username
+                    )
+                })?
+        };
+
+        info!(
+            "Found {} memberships for user {}",
+            memberships.len(),
+            from_user.username
+        );
+
+        let mut results = Vec::new();
+
+        for membership in &memberships {
+            let name = &membership.source_name;
+
+            let Some(level) = self.capped_role(membership.access_level, args.max_role.as_ref())
+            else {
+                results.push(ProjectResult::skipped(
+                    name,
+                    format!("unknown access level {}", membership.access_level),
+                ));
+                continue;
+            };
+
+            if membership.is_project() {
+                match client
+                    .users()
+                    .get_project_member(to_user.id, membership.source_id)
+                    .await
+                {
+                    Ok(Some(_)) => {
+                        results.push(ProjectResult::skipped(
+                            name,
+                            format!("{} is already a member", to_user.username),
+                        ));
+                        continue;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        results.push(ProjectResult::failed(
+                            name,
+                            format!("failed to check membership: {:#}", e),
+                        ));
+                        continue;
+                    }
+                }
+            }
+
+            if args.dry_run {
+                results.push(ProjectResult::dry_run(
+                    name,
+                    format!("would add {} as {}", to_user.username, level),
+                ));
+                continue;
+            }
+
+            info!("Adding user {} to {}", to_user.username, name);
+
+            let result = if membership.is_project() {
+                client
+                    .users()
+                    .add_to_project(to_user.id, membership.source_id, level.clone())
+                    .await
+            } else {
+                client
+                    .users()
+                    .add_to_group(to_user.id, membership.source_id, level.clone())
+                    .await
+                    .map(|()| AddedAs::Member)
+            };
+
+            results.push(match result {
+                Ok(added_as) => ProjectResult::success(
+                    name,
+                    self.added_message(&to_user.username, &level, &added_as),
+                ),
+                Err(e) => ProjectResult::failed(name, format!("{:#}", e)),
+            });
+        }
+
+        results::report(&results)
+    }
+
+    /// Make sure `successor` holds at least `level` in the membership's project or group,
+    /// raising an existing membership rather than adding the successor again. Fails when
+    /// the successor would only get a pending invitation, since the user is removed next.
+    async fn hand_over(
+        &self,
+        client: &GitLabClient,
+        successor: &User,
+        membership: &Membership,
+        level: &AccessLevel,
+    ) -> Result<String> {
+        let users = client.users();
+        let source_id = membership.source_id;
+
+        let current = if membership.is_project() {
+            users.get_project_member(successor.id, source_id).await?
+        } else {
+            users.get_group_member(successor.id, source_id).await?
+        };
+
+        if let Some(member) = current {
+            if member.state != "active" {
+                anyhow::bail!("{} is {}", successor.username, member.state);
+            }
+            if member.access_level >= level.as_u64() {
+                return Ok(format!(
+                    "{} already has {} access",
+                    successor.username, level
+                ));
+            }
+
+            info!(
+                "Raising {} to {} access in {}",
+                successor.username, level, membership.source_name
+            );
+            if membership.is_project() {
+                users
+                    .update_project_member(successor.id, source_id, level.clone())
+                    .await?;
+            } else {
+                users
+                    .update_group_member(successor.id, source_id, level.clone())
+                    .await?;
+            }
+            return Ok(format!("raised {} to {}", successor.username, level));
+        }
+
+        info!(
+            "Granting {} {} access to {}",
+            successor.username, level, membership.source_name
+        );
+        if membership.is_project() {
+            let added_as = users
+                .add_to_project(successor.id, source_id, level.clone())
+                .await?;
+            if added_as == AddedAs::Invitation {
+                anyhow::bail!(
+                    "{} was only invited; access is pending until the invitation is accepted",
+                    successor.username
+                );
+            }
+        } else {
+            users
+                .add_to_group(successor.id, source_id, level.clone())
+                .await?;
+        }
+
+        Ok(format!("granted {} {}", successor.username, level))
+    }
+
+    /// Whether removing `user` would leave the project without an active Owner or Maintainer
+    async fn would_orphan_project(
+        &self,
+        client: &GitLabClient,
+        user: &User,
+        project_id: u64,
+    ) -> Result<bool> {
+        let members = client
+            .users()
+            .list_all_project_members(project_id)
+            .await
+            .with_context(|| format!("Failed to list members of project {}", project_id))?;
+
+        Ok(member::would_orphan(&members, user.id))
+    }
+
+    fn added_message(&self, username: &str, level: &AccessLevel, added_as: &AddedAs) -> String {
+        match added_as {
+            AddedAs::Member => format!("added {} as {}", username, level),
+            AddedAs::Invitation => {
+                format!("invited {} as {} (pending invitation)", username, level)
+            }
+        }
+    }
+
+    /// Convert a raw access level, lowering it to `max_role` when it is higher
+    fn capped_role(
+        &self,
+        access_level: u64,
+        max_role: Option<&AccessLevel>,
+    ) -> Option<AccessLevel> {
+        let level = AccessLevel::from_u64(access_level)?;
+
+        match max_role {
+            Some(max_role) if level > *max_role => Some(max_role.clone()),
+            _ => Some(level),
+        }
+    }
+
+    async fn scan_project_memberships(
+        &self,
+        client: &GitLabClient,
+        user: &User,
+        selection: &ProjectSelectionArgs,
+    ) -> Result<Vec<Membership>> {
+        let projects = selection.resolve(client).await?;
+
+        info!(
+            "Scanning {} projects for memberships of {}",
+            projects.len(),
+            user.username
+        );
+
+        let mut memberships = Vec::new();
+
+        for project in &projects {
+            let member = client
+                .users()
+                .get_project_member(user.id, project.id)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to check membership in project {}",
+                        project.path_with_namespace
+                    )
+                })?;
+
+            if let Some(member) = member {
+                memberships.push(Membership {
+                    source_id: project.id,
+                    source_name: project.path_with_namespace.clone(),
+                    source_type: "Project".to_string(),
+                    access_level: member.access_level,
+                });
+            }
+        }
+
+        Ok(memberships)
+    }
+
+    async fn resolve_user(&self, client: &GitLabClient, id_or_username: &str) -> Result<User> {
+        let users = self
+            .resolve_user_ids(client, &[id_or_username.to_string()])
+            .await?;
+
+        users
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("User not found: {}", id_or_username))
+    }
+
     fn load_users_from_file(&self, file_path: &PathBuf) -> Result<Vec<crate::models::user::User>> {
         let extension = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
 
@@ -267,4 +962,3 @@ impl UserCommands {
         Ok(projects)
     }
 }
-
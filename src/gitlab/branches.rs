@@ -0,0 +1,244 @@
+use anyhow::Result;
+use serde_json::json;
+use tracing::debug;
+
+use super::client::GitLabClient;
+use crate::models::branch::{Branch, NewProtectedBranch, ProtectedBranch, ProtectedBranchUpdate};
+
+pub struct BranchesApi<'a> {
+    client: &'a GitLabClient,
+}
+
+impl<'a> BranchesApi<'a> {
+    pub fn new(client: &'a GitLabClient) -> Self {
+        Self { client }
+    }
+
+    pub async fn get(&self, project_id: u64, branch: &str) -> Result<Option<Branch>> {
+        let url = format!(
+            "{}/projects/{}/repository/branches/{}",
+            self.client.api_url(),
+            project_id,
+            urlencoding::encode(branch)
+        );
+
+        debug!("Fetching branch {} in project {}", branch, project_id);
+
+        let response = self.client.http_client().get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let branch = response.error_for_status()?.json::<Branch>().await?;
+
+        Ok(Some(branch))
+    }
+
+    pub async fn create(&self, project_id: u64, branch: &str, from_ref: &str) -> Result<Branch> {
+        let url = format!(
+            "{}/projects/{}/repository/branches",
+            self.client.api_url(),
+            project_id
+        );
+
+        debug!(
+            "Creating branch {} from {} in project {}",
+            branch, from_ref, project_id
+        );
+
+        let body = json!({
+            "branch": branch,
+            "ref": from_ref,
+        });
+
+        let response = self
+            .client
+            .http_client()
+            .post(&url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Branch>()
+            .await?;
+
+        Ok(response)
+    }
+
+    /// List the branches of a project, optionally only those matching `search`
+    pub async fn list(&self, project_id: u64, search: Option<&str>) -> Result<Vec<Branch>> {
+        let mut url = format!(
+            "{}/projects/{}/repository/branches?per_page=100",
+            self.client.api_url(),
+            project_id
+        );
+        if let Some(search) = search {
+            url.push_str(&format!("&search={}", urlencoding::encode(search)));
+        }
+
+        debug!("Listing branches in project {}", project_id);
+
+        let mut all_branches = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let page_url = format!("{}&page={}", url, page);
+
+            let branches: Vec<Branch> = self
+                .client
+                .http_client()
+                .get(&page_url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            if branches.is_empty() {
+                break;
+            }
+
+            all_branches.extend(branches);
+            page += 1;
+        }
+
+        Ok(all_branches)
+    }
+
+    pub async fn delete(&self, project_id: u64, branch: &str) -> Result<()> {
+        let url = format!(
+            "{}/projects/{}/repository/branches/{}",
+            self.client.api_url(),
+            project_id,
+            urlencoding::encode(branch)
+        );
+
+        debug!("Deleting branch {} in project {}", branch, project_id);
+
+        self.client
+            .http_client()
+            .delete(&url)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    /// Get the protection rule of a branch; `None` when it is not protected
+    pub async fn get_protected(
+        &self,
+        project_id: u64,
+        branch: &str,
+    ) -> Result<Option<ProtectedBranch>> {
+        let url = format!(
+            "{}/projects/{}/protected_branches/{}",
+            self.client.api_url(),
+            project_id,
+            urlencoding::encode(branch)
+        );
+
+        debug!(
+            "Fetching protection of branch {} in project {}",
+            branch, project_id
+        );
+
+        let response = self.client.http_client().get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let protected = response
+            .error_for_status()?
+            .json::<ProtectedBranch>()
+            .await?;
+
+        Ok(Some(protected))
+    }
+
+    /// Protect a branch or wildcard pattern
+    pub async fn protect(
+        &self,
+        project_id: u64,
+        protection: &NewProtectedBranch,
+    ) -> Result<ProtectedBranch> {
+        let url = format!(
+            "{}/projects/{}/protected_branches",
+            self.client.api_url(),
+            project_id
+        );
+
+        debug!(
+            "Protecting branch {} in project {}",
+            protection.name, project_id
+        );
+
+        let response = self
+            .client
+            .http_client()
+            .post(&url)
+            .json(protection)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ProtectedBranch>()
+            .await?;
+
+        Ok(response)
+    }
+
+    /// Change the settings of a protected branch in place
+    pub async fn update_protected(
+        &self,
+        project_id: u64,
+        branch: &str,
+        update: &ProtectedBranchUpdate,
+    ) -> Result<ProtectedBranch> {
+        let url = format!(
+            "{}/projects/{}/protected_branches/{}",
+            self.client.api_url(),
+            project_id,
+            urlencoding::encode(branch)
+        );
+
+        debug!(
+            "Updating protection of branch {} in project {}",
+            branch, project_id
+        );
+
+        let response = self
+            .client
+            .http_client()
+            .patch(&url)
+            .json(update)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ProtectedBranch>()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn unprotect(&self, project_id: u64, branch: &str) -> Result<()> {
+        let url = format!(
+            "{}/projects/{}/protected_branches/{}",
+            self.client.api_url(),
+            project_id,
+            urlencoding::encode(branch)
+        );
+
+        debug!("Unprotecting branch {} in project {}", branch, project_id);
+
+        self.client
+            .http_client()
+            .delete(&url)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
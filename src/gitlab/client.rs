@@ -1,11 +1,14 @@
 use reqwest::{Client, header};
 use tracing::debug;
 
-use super::{files::FilesApi, projects::ProjectsApi, users::UsersApi};
+use super::{
+    branches::BranchesApi, commits::CommitsApi, files::FilesApi, groups::GroupsApi,
+    invitations::InvitationsApi, merge_requests::MergeRequestsApi, projects::ProjectsApi,
+    search::SearchApi, users::UsersApi,
+};
 
 pub struct GitLabClient {
     api_url: String,
-    api_token: String,
     http_client: Client,
 }
 
@@ -26,23 +29,46 @@ impl GitLabClient {
 
         Self {
             api_url: api_url.to_string(),
-            api_token: api_token.to_string(),
             http_client,
         }
     }
 
-    pub fn projects(&self) -> ProjectsApi {
+    pub fn projects(&self) -> ProjectsApi<'_> {
         ProjectsApi::new(self)
     }
 
-    pub fn users(&self) -> UsersApi {
+    pub fn users(&self) -> UsersApi<'_> {
         UsersApi::new(self)
     }
 
-    pub fn files(&self) -> FilesApi {
+    pub fn files(&self) -> FilesApi<'_> {
         FilesApi::new(self)
     }
 
+    pub fn groups(&self) -> GroupsApi<'_> {
+        GroupsApi::new(self)
+    }
+
+    pub fn invitations(&self) -> InvitationsApi<'_> {
+        InvitationsApi::new(self)
+    }
+
+    pub fn branches(&self) -> BranchesApi<'_> {
+        BranchesApi::new(self)
+    }
+
+    pub fn merge_requests(&self) -> MergeRequestsApi<'_> {
+        MergeRequestsApi::new(self)
+    }
+
+    pub fn commits(&self) -> CommitsApi<'_> {
+        CommitsApi::new(self)
+    }
+
+    pub fn search(&self) -> SearchApi<'_> {
+        SearchApi::new(self)
+    }
+
     pub fn api_url(&self) -> &str {
         &self.api_url
     }
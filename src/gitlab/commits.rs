@@ -0,0 +1,44 @@
+use anyhow::Result;
+use tracing::debug;
+
+use super::client::GitLabClient;
+use super::files::check_conflict;
+use crate::models::commit::{Commit, NewCommit};
+
+pub struct CommitsApi<'a> {
+    client: &'a GitLabClient,
+}
+
+impl<'a> CommitsApi<'a> {
+    pub fn new(client: &'a GitLabClient) -> Self {
+        Self { client }
+    }
+
+    /// Create a single commit applying all of the given actions atomically
+    pub async fn create(&self, project_id: u64, commit: &NewCommit) -> Result<Commit> {
+        let url = format!(
+            "{}/projects/{}/repository/commits",
+            self.client.api_url(),
+            project_id
+        );
+
+        debug!(
+            "Creating commit with {} actions on branch {} in project {}",
+            commit.actions.len(),
+            commit.branch,
+            project_id
+        );
+
+        let response = self
+            .client
+            .http_client()
+            .post(&url)
+            .json(commit)
+            .send()
+            .await?;
+
+        let response = check_conflict(response).await?.json::<Commit>().await?;
+
+        Ok(response)
+    }
+}
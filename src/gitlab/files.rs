@@ -1,10 +1,12 @@
 use anyhow::Result;
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 use serde::Deserialize;
-use serde_json::json;
+use serde_json::{Value, json};
 use tracing::debug;
 
 use super::client::GitLabClient;
+use crate::models::commit::CommitDetails;
+use crate::models::file::{FileConflict, FileMetadata, RepositoryFile};
 
 pub struct FilesApi<'a> {
     client: &'a GitLabClient,
@@ -14,6 +16,12 @@ pub struct FilesApi<'a> {
 struct FileResponse {
     file_path: String,
     content: String, // Base64 encoded
+    blob_id: String,
+    #[serde(default)]
+    content_sha256: String,
+    last_commit_id: String,
+    #[serde(default)]
+    size: u64,
 }
 
 impl<'a> FilesApi<'a> {
@@ -21,47 +29,65 @@ impl<'a> FilesApi<'a> {
         Self { client }
     }
 
-    pub async fn file_exists(
+    /// Fetch the file's metadata without its content; `None` when the file does not exist
+    pub async fn get_file_metadata(
         &self,
         project_id: u64,
         file_path: &str,
         branch: &str,
-    ) -> Result<bool> {
+    ) -> Result<Option<FileMetadata>> {
         let encoded_path = urlencoding::encode(file_path);
         let url = format!(
             "{}/projects/{}/repository/files/{}?ref={}",
             self.client.api_url(),
             project_id,
             encoded_path,
-            branch
+            urlencoding::encode(branch)
         );
 
         debug!(
-            "Checking if file exists: {} in project {} branch {}",
+            "Fetching file metadata: {} in project {} branch {}",
             file_path, project_id, branch
         );
 
-        let response = self.client.http_client().get(&url).send().await;
+        let response = self.client.http_client().head(&url).send().await?;
 
-        match response {
-            Ok(response) => Ok(response.status().is_success()),
-            Err(_) => Ok(false),
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
         }
+
+        let response = response.error_for_status()?;
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+                .to_string()
+        };
+
+        Ok(Some(FileMetadata {
+            blob_id: header("X-Gitlab-Blob-Id"),
+            content_sha256: header("X-Gitlab-Content-Sha256"),
+            last_commit_id: header("X-Gitlab-Last-Commit-Id"),
+            size: header("X-Gitlab-Size").parse().unwrap_or_default(),
+        }))
     }
 
-    pub async fn get_file_content(
+    /// Fetch the file with its content and metadata; `None` when the file does not exist
+    pub async fn get_file(
         &self,
         project_id: u64,
         file_path: &str,
         branch: &str,
-    ) -> Result<String> {
+    ) -> Result<Option<RepositoryFile>> {
         let encoded_path = urlencoding::encode(file_path);
         let url = format!(
             "{}/projects/{}/repository/files/{}?ref={}",
             self.client.api_url(),
             project_id,
             encoded_path,
-            branch
+            urlencoding::encode(branch)
         );
 
         debug!(
@@ -69,21 +95,59 @@ impl<'a> FilesApi<'a> {
             file_path, project_id, branch
         );
 
-        let response: FileResponse = self
-            .client
-            .http_client()
-            .get(&url)
-            .send()
-            .await?
-            .error_for_status()?
-            .json()
-            .await?;
+        let response = self.client.http_client().get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let response: FileResponse = response.error_for_status()?.json().await?;
+
+        // Decode base64 content; the bytes may be binary
+        let content = BASE64.decode(response.content)?;
+
+        Ok(Some(RepositoryFile {
+            file_path: response.file_path,
+            content,
+            metadata: FileMetadata {
+                blob_id: response.blob_id,
+                content_sha256: response.content_sha256,
+                last_commit_id: response.last_commit_id,
+                size: response.size,
+            },
+        }))
+    }
+
+    /// Download the raw bytes of a file; `None` when the file does not exist
+    pub async fn get_raw_file(
+        &self,
+        project_id: u64,
+        file_path: &str,
+        branch: &str,
+    ) -> Result<Option<Vec<u8>>> {
+        let encoded_path = urlencoding::encode(file_path);
+        let url = format!(
+            "{}/projects/{}/repository/files/{}/raw?ref={}",
+            self.client.api_url(),
+            project_id,
+            encoded_path,
+            urlencoding::encode(branch)
+        );
+
+        debug!(
+            "Downloading raw file: {} in project {} branch {}",
+            file_path, project_id, branch
+        );
+
+        let response = self.client.http_client().get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
 
-        // Decode base64 content
-        let decoded = BASE64.decode(response.content)?;
-        let content = String::from_utf8(decoded)?;
+        let bytes = response.error_for_status()?.bytes().await?;
 
-        Ok(content)
+        Ok(Some(bytes.to_vec()))
     }
 
     pub async fn create_file(
@@ -91,8 +155,8 @@ impl<'a> FilesApi<'a> {
         project_id: u64,
         file_path: &str,
         branch: &str,
-        commit_message: &str,
-        content: &str,
+        commit: &CommitDetails,
+        content: &[u8],
     ) -> Result<()> {
         let encoded_path = urlencoding::encode(file_path);
         let url = format!(
@@ -108,13 +172,14 @@ impl<'a> FilesApi<'a> {
         );
 
         // Encode content as base64
-        let encoded_content = BASE64.encode(content.as_bytes());
+        let encoded_content = BASE64.encode(content);
 
-        let body = json!({
+        let mut body = json!({
             "branch": branch,
             "content": encoded_content,
-            "commit_message": commit_message,
+            "encoding": "base64",
         });
+        add_commit_details(&mut body, commit)?;
 
         self.client
             .http_client()
@@ -132,8 +197,9 @@ impl<'a> FilesApi<'a> {
         project_id: u64,
         file_path: &str,
         branch: &str,
-        commit_message: &str,
-        content: &str,
+        commit: &CommitDetails,
+        content: &[u8],
+        last_commit_id: Option<&str>,
     ) -> Result<()> {
         let encoded_path = urlencoding::encode(file_path);
         let url = format!(
@@ -149,22 +215,96 @@ impl<'a> FilesApi<'a> {
         );
 
         // Encode content as base64
-        let encoded_content = BASE64.encode(content.as_bytes());
+        let encoded_content = BASE64.encode(content);
 
-        let body = json!({
+        let mut body = json!({
             "branch": branch,
             "content": encoded_content,
-            "commit_message": commit_message,
+            "encoding": "base64",
         });
+        add_commit_details(&mut body, commit)?;
+        // GitLab rejects the update if the file changed after this commit
+        if let Some(last_commit_id) = last_commit_id {
+            body["last_commit_id"] = json!(last_commit_id);
+        }
 
-        self.client
+        let response = self
+            .client
             .http_client()
             .put(&url)
             .json(&body)
             .send()
-            .await?
-            .error_for_status()?;
+            .await?;
+
+        check_conflict(response).await?;
 
         Ok(())
     }
+
+    pub async fn delete_file(
+        &self,
+        project_id: u64,
+        file_path: &str,
+        branch: &str,
+        commit: &CommitDetails,
+        last_commit_id: Option<&str>,
+    ) -> Result<()> {
+        let encoded_path = urlencoding::encode(file_path);
+        let url = format!(
+            "{}/projects/{}/repository/files/{}",
+            self.client.api_url(),
+            project_id,
+            encoded_path
+        );
+
+        debug!(
+            "Deleting file: {} in project {} branch {}",
+            file_path, project_id, branch
+        );
+
+        let mut body = json!({
+            "branch": branch,
+        });
+        add_commit_details(&mut body, commit)?;
+        if let Some(last_commit_id) = last_commit_id {
+            body["last_commit_id"] = json!(last_commit_id);
+        }
+
+        let response = self
+            .client
+            .http_client()
+            .delete(&url)
+            .json(&body)
+            .send()
+            .await?;
+
+        check_conflict(response).await?;
+
+        Ok(())
+    }
+}
+
+/// Add the commit message and optional author to a request body
+fn add_commit_details(body: &mut Value, commit: &CommitDetails) -> Result<()> {
+    if let (Value::Object(body), Value::Object(details)) = (body, serde_json::to_value(commit)?) {
+        body.extend(details);
+    }
+    Ok(())
+}
+
+/// Turn a rejected `last_commit_id` into a `FileConflict`, and other failures into errors
+pub(crate) async fn check_conflict(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let url = response.url().clone();
+    let body = response.text().await.unwrap_or_default();
+
+    if let Some(conflict) = FileConflict::from_response(status, &body) {
+        return Err(conflict.into());
+    }
+
+    anyhow::bail!("HTTP status {} for url ({}): {}", status, url, body)
 }
@@ -0,0 +1,53 @@
+use anyhow::Result;
+use tracing::debug;
+
+use super::client::GitLabClient;
+use crate::models::group::Group;
+
+pub struct GroupsApi<'a> {
+    client: &'a GitLabClient,
+}
+
+impl<'a> GroupsApi<'a> {
+    pub fn new(client: &'a GitLabClient) -> Self {
+        Self { client }
+    }
+
+    pub async fn get_by_id(&self, id: u64) -> Result<Group> {
+        let url = format!("{}/groups/{}", self.client.api_url(), id);
+
+        debug!("Fetching group by ID: {}", id);
+
+        let response = self
+            .client
+            .http_client()
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Group>()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn get_by_path(&self, path: &str) -> Result<Group> {
+        // URL encode the path
+        let encoded_path = urlencoding::encode(path);
+        let url = format!("{}/groups/{}", self.client.api_url(), encoded_path);
+
+        debug!("Fetching group by path: {}", path);
+
+        let response = self
+            .client
+            .http_client()
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Group>()
+            .await?;
+
+        Ok(response)
+    }
+}
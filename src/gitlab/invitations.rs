@@ -0,0 +1,160 @@
+use anyhow::Result;
+use serde_json::json;
+use tracing::debug;
+
+use super::client::GitLabClient;
+use crate::models::invitation::Invitation;
+use crate::models::user::AccessLevel;
+
+pub struct InvitationsApi<'a> {
+    client: &'a GitLabClient,
+}
+
+impl<'a> InvitationsApi<'a> {
+    pub fn new(client: &'a GitLabClient) -> Self {
+        Self { client }
+    }
+
+    pub async fn list(&self, project_id: u64) -> Result<Vec<Invitation>> {
+        let url = format!(
+            "{}/projects/{}/invitations?per_page=100",
+            self.client.api_url(),
+            project_id
+        );
+
+        debug!("Listing invitations for project {}", project_id);
+
+        let mut all_invitations = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let page_url = format!("{}&page={}", url, page);
+
+            let invitations: Vec<Invitation> = self
+                .client
+                .http_client()
+                .get(&page_url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            if invitations.is_empty() {
+                break;
+            }
+
+            all_invitations.extend(invitations);
+            page += 1;
+        }
+
+        debug!(
+            "Found {} invitations for project {}",
+            all_invitations.len(),
+            project_id
+        );
+
+        Ok(all_invitations)
+    }
+
+    pub async fn create(
+        &self,
+        project_id: u64,
+        email: &str,
+        access_level: AccessLevel,
+        expires_at: Option<&str>,
+    ) -> Result<()> {
+        let url = format!(
+            "{}/projects/{}/invitations",
+            self.client.api_url(),
+            project_id
+        );
+
+        debug!("Inviting {} to project {}", email, project_id);
+
+        let mut body = json!({
+            "email": email,
+            "access_level": access_level.as_u64(),
+        });
+
+        if let Some(expires_at) = expires_at {
+            body["expires_at"] = json!(expires_at);
+        }
+
+        let response = self
+            .client
+            .http_client()
+            .post(&url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        // GitLab reports per-email failures with a 201 and an error status in the body
+        let result: serde_json::Value = response.json().await.unwrap_or_default();
+        if result.get("status").and_then(|s| s.as_str()) == Some("error") {
+            anyhow::bail!("Failed to invite {}: {}", email, result["message"]);
+        }
+
+        Ok(())
+    }
+
+    pub async fn update(
+        &self,
+        project_id: u64,
+        email: &str,
+        access_level: Option<AccessLevel>,
+        expires_at: Option<&str>,
+    ) -> Result<Invitation> {
+        let url = format!(
+            "{}/projects/{}/invitations/{}",
+            self.client.api_url(),
+            project_id,
+            urlencoding::encode(email)
+        );
+
+        debug!("Updating invitation of {} to project {}", email, project_id);
+
+        let mut body = json!({});
+
+        if let Some(access_level) = access_level {
+            body["access_level"] = json!(access_level.as_u64());
+        }
+        if let Some(expires_at) = expires_at {
+            body["expires_at"] = json!(expires_at);
+        }
+
+        let response = self
+            .client
+            .http_client()
+            .put(&url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Invitation>()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn revoke(&self, project_id: u64, email: &str) -> Result<()> {
+        let url = format!(
+            "{}/projects/{}/invitations/{}",
+            self.client.api_url(),
+            project_id,
+            urlencoding::encode(email)
+        );
+
+        debug!("Revoking invitation of {} to project {}", email, project_id);
+
+        self.client
+            .http_client()
+            .delete(&url)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use anyhow::Result;
+use serde_json::json;
+use tracing::debug;
+
+use super::client::GitLabClient;
+use crate::models::merge_request::{MergeRequest, NewMergeRequest};
+
+pub struct MergeRequestsApi<'a> {
+    client: &'a GitLabClient,
+}
+
+impl<'a> MergeRequestsApi<'a> {
+    pub fn new(client: &'a GitLabClient) -> Self {
+        Self { client }
+    }
+
+    pub async fn create(
+        &self,
+        project_id: u64,
+        merge_request: &NewMergeRequest,
+    ) -> Result<MergeRequest> {
+        let url = format!(
+            "{}/projects/{}/merge_requests",
+            self.client.api_url(),
+            project_id
+        );
+
+        debug!(
+            "Creating merge request {} -> {} in project {}",
+            merge_request.source_branch, merge_request.target_branch, project_id
+        );
+
+        let response = self
+            .client
+            .http_client()
+            .post(&url)
+            .json(merge_request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<MergeRequest>()
+            .await?;
+
+        Ok(response)
+    }
+
+    /// Merge the merge request automatically once its pipeline succeeds
+    pub async fn merge_when_pipeline_succeeds(&self, project_id: u64, iid: u64) -> Result<()> {
+        let url = format!(
+            "{}/projects/{}/merge_requests/{}/merge",
+            self.client.api_url(),
+            project_id,
+            iid
+        );
+
+        debug!(
+            "Setting merge request !{} in project {} to merge when pipeline succeeds",
+            iid, project_id
+        );
+
+        let body = json!({
+            "merge_when_pipeline_succeeds": true,
+        });
+
+        self.client
+            .http_client()
+            .put(&url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    /// List the open merge requests targeting `target_branch`
+    pub async fn list_open(
+        &self,
+        project_id: u64,
+        target_branch: &str,
+    ) -> Result<Vec<MergeRequest>> {
+        let url = format!(
+            "{}/projects/{}/merge_requests?state=opened&target_branch={}&per_page=100",
+            self.client.api_url(),
+            project_id,
+            urlencoding::encode(target_branch)
+        );
+
+        debug!(
+            "Listing open merge requests into {} in project {}",
+            target_branch, project_id
+        );
+
+        let mut all_merge_requests = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let page_url = format!("{}&page={}", url, page);
+
+            let merge_requests: Vec<MergeRequest> = self
+                .client
+                .http_client()
+                .get(&page_url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            if merge_requests.is_empty() {
+                break;
+            }
+
+            all_merge_requests.extend(merge_requests);
+            page += 1;
+        }
+
+        Ok(all_merge_requests)
+    }
+
+    /// The open merge request from `source_branch`, if there is one
+    pub async fn find_open_from(
+        &self,
+        project_id: u64,
+        source_branch: &str,
+    ) -> Result<Option<MergeRequest>> {
+        let url = format!(
+            "{}/projects/{}/merge_requests?state=opened&source_branch={}",
+            self.client.api_url(),
+            project_id,
+            urlencoding::encode(source_branch)
+        );
+
+        debug!(
+            "Looking up open merge request from {} in project {}",
+            source_branch, project_id
+        );
+
+        let merge_requests: Vec<MergeRequest> = self
+            .client
+            .http_client()
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(merge_requests.into_iter().next())
+    }
+
+    pub async fn set_target_branch(
+        &self,
+        project_id: u64,
+        iid: u64,
+        target_branch: &str,
+    ) -> Result<MergeRequest> {
+        let url = format!(
+            "{}/projects/{}/merge_requests/{}",
+            self.client.api_url(),
+            project_id,
+            iid
+        );
+
+        debug!(
+            "Retargeting merge request !{} in project {} to {}",
+            iid, project_id, target_branch
+        );
+
+        let body = json!({
+            "target_branch": target_branch,
+        });
+
+        let response = self
+            .client
+            .http_client()
+            .put(&url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<MergeRequest>()
+            .await?;
+
+        Ok(response)
+    }
+}
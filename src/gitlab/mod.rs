@@ -1,4 +1,10 @@
+pub mod branches;
 pub mod client;
+pub mod commits;
 pub mod files;
+pub mod groups;
+pub mod invitations;
+pub mod merge_requests;
 pub mod projects;
+pub mod search;
 pub mod users;
@@ -1,9 +1,10 @@
 use anyhow::Result;
-use serde_json::json;
+use serde_json::{Map, Value, json};
 use tracing::debug;
 
 use super::client::GitLabClient;
 use crate::models::project::Project;
+use crate::models::user::AccessLevel;
 
 pub struct ProjectsApi<'a> {
     client: &'a GitLabClient,
@@ -120,6 +121,78 @@ impl<'a> ProjectsApi<'a> {
 
         Ok(response)
     }
+    /// Fetch the project as raw JSON, including its settings
+    pub async fn get_settings(&self, project_id: u64) -> Result<Value> {
+        let url = format!("{}/projects/{}", self.client.api_url(), project_id);
+
+        debug!("Fetching settings of project {}", project_id);
+
+        let response = self
+            .client
+            .http_client()
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Value>()
+            .await?;
+
+        Ok(response)
+    }
+
+    /// Change project settings, returning the project as raw JSON
+    pub async fn update_settings(
+        &self,
+        project_id: u64,
+        settings: &Map<String, Value>,
+    ) -> Result<Value> {
+        let url = format!("{}/projects/{}", self.client.api_url(), project_id);
+
+        debug!(
+            "Updating settings of project {}: {:?}",
+            project_id, settings
+        );
+
+        let response = self
+            .client
+            .http_client()
+            .put(&url)
+            .json(settings)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Value>()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn set_default_branch(&self, project_id: u64, branch: &str) -> Result<Project> {
+        let url = format!("{}/projects/{}", self.client.api_url(), project_id);
+
+        debug!(
+            "Setting default branch of project {} to {}",
+            project_id, branch
+        );
+
+        let body = json!({
+            "default_branch": branch,
+        });
+
+        let response = self
+            .client
+            .http_client()
+            .put(&url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Project>()
+            .await?;
+
+        Ok(response)
+    }
+
     // In src/gitlab/projects.rs:
     pub async fn list(&self) -> Result<Vec<Project>> {
         let url = format!("{}/projects?per_page=100", self.client.api_url());
@@ -158,4 +231,138 @@ impl<'a> ProjectsApi<'a> {
 
         Ok(all_projects)
     }
+
+    pub async fn share_with_group(
+        &self,
+        project_id: u64,
+        group_id: u64,
+        group_access: AccessLevel,
+        expires_at: Option<&str>,
+    ) -> Result<()> {
+        let url = format!("{}/projects/{}/share", self.client.api_url(), project_id);
+
+        debug!(
+            "Sharing project {} with group {} at access level {:?}",
+            project_id, group_id, group_access
+        );
+
+        let mut body = json!({
+            "group_id": group_id,
+            "group_access": group_access.as_u64(),
+        });
+
+        if let Some(expires_at) = expires_at {
+            body["expires_at"] = json!(expires_at);
+        }
+
+        self.client
+            .http_client()
+            .post(&url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    pub async fn unshare_with_group(&self, project_id: u64, group_id: u64) -> Result<()> {
+        let url = format!(
+            "{}/projects/{}/share/{}",
+            self.client.api_url(),
+            project_id,
+            group_id
+        );
+
+        debug!(
+            "Removing share of project {} with group {}",
+            project_id, group_id
+        );
+
+        self.client
+            .http_client()
+            .delete(&url)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    pub async fn archive(&self, project_id: u64) -> Result<()> {
+        let url = format!("{}/projects/{}/archive", self.client.api_url(), project_id);
+
+        debug!("Archiving project {}", project_id);
+
+        self.client
+            .http_client()
+            .post(&url)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    pub async fn unarchive(&self, project_id: u64) -> Result<()> {
+        let url = format!(
+            "{}/projects/{}/unarchive",
+            self.client.api_url(),
+            project_id
+        );
+
+        debug!("Unarchiving project {}", project_id);
+
+        self.client
+            .http_client()
+            .post(&url)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    /// Move the project to another namespace, returning it with its new path
+    pub async fn transfer(&self, project_id: u64, namespace_id: u64) -> Result<Project> {
+        let url = format!("{}/projects/{}/transfer", self.client.api_url(), project_id);
+
+        debug!(
+            "Transferring project {} to namespace {}",
+            project_id, namespace_id
+        );
+
+        let body = json!({
+            "namespace": namespace_id,
+        });
+
+        let response = self
+            .client
+            .http_client()
+            .put(&url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Project>()
+            .await?;
+
+        Ok(response)
+    }
+
+    /// Delete the project; instances with delayed deletion only mark it for deletion
+    pub async fn delete(&self, project_id: u64) -> Result<()> {
+        let url = format!("{}/projects/{}", self.client.api_url(), project_id);
+
+        debug!("Deleting project {}", project_id);
+
+        self.client
+            .http_client()
+            .delete(&url)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
 }
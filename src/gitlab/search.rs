@@ -0,0 +1,94 @@
+use anyhow::Result;
+use tracing::debug;
+
+use super::client::GitLabClient;
+use crate::models::search::BlobMatch;
+
+pub struct SearchApi<'a> {
+    client: &'a GitLabClient,
+}
+
+impl<'a> SearchApi<'a> {
+    pub fn new(client: &'a GitLabClient) -> Self {
+        Self { client }
+    }
+
+    /// Search file contents of a project, optionally on a specific branch
+    pub async fn project_blobs(
+        &self,
+        project_id: u64,
+        search: &str,
+        branch: Option<&str>,
+    ) -> Result<Vec<BlobMatch>> {
+        let mut url = format!(
+            "{}/projects/{}/search?scope=blobs&search={}",
+            self.client.api_url(),
+            project_id,
+            urlencoding::encode(search)
+        );
+        if let Some(branch) = branch {
+            url.push_str(&format!("&ref={}", urlencoding::encode(branch)));
+        }
+
+        debug!("Searching blobs in project {}: {}", project_id, search);
+
+        self.fetch_blobs(&url).await
+    }
+
+    /// Search file contents of all projects in a group (requires advanced search)
+    pub async fn group_blobs(&self, group_id: u64, search: &str) -> Result<Vec<BlobMatch>> {
+        let url = format!(
+            "{}/groups/{}/search?scope=blobs&search={}",
+            self.client.api_url(),
+            group_id,
+            urlencoding::encode(search)
+        );
+
+        debug!("Searching blobs in group {}: {}", group_id, search);
+
+        self.fetch_blobs(&url).await
+    }
+
+    /// Search file contents across the instance (requires advanced search)
+    pub async fn global_blobs(&self, search: &str) -> Result<Vec<BlobMatch>> {
+        let url = format!(
+            "{}/search?scope=blobs&search={}",
+            self.client.api_url(),
+            urlencoding::encode(search)
+        );
+
+        debug!("Searching blobs globally: {}", search);
+
+        self.fetch_blobs(&url).await
+    }
+
+    async fn fetch_blobs(&self, url: &str) -> Result<Vec<BlobMatch>> {
+        let mut all_blobs = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let page_url = format!("{}&per_page=100&page={}", url, page);
+
+            let blobs: Vec<BlobMatch> = self
+                .client
+                .http_client()
+                .get(&page_url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            if blobs.is_empty() {
+                break;
+            }
+
+            all_blobs.extend(blobs);
+            page += 1;
+        }
+
+        debug!("Found {} matching blobs", all_blobs.len());
+
+        Ok(all_blobs)
+    }
+}
@@ -1,8 +1,9 @@
 use anyhow::Result;
 use serde_json::json;
-use tracing::debug;
+use tracing::{debug, warn};
 
 use super::client::GitLabClient;
+use crate::models::member::{AddedAs, Member, Membership};
 use crate::models::user::{AccessLevel, User};
 
 pub struct UsersApi<'a> {
@@ -59,7 +60,7 @@ impl<'a> UsersApi<'a> {
         user_id: u64,
         project_id: u64,
         access_level: AccessLevel,
-    ) -> Result<()> {
+    ) -> Result<AddedAs> {
         // First, try the members endpoint (works for self-managed GitLab instances)
         let members_url = format!("{}/projects/{}/members", self.client.api_url(), project_id);
 
@@ -85,7 +86,7 @@ impl<'a> UsersApi<'a> {
             Ok(response) => {
                 if response.status().is_success() {
                     debug!("Successfully added user to project using members endpoint");
-                    return Ok(());
+                    return Ok(AddedAs::Member);
                 }
 
                 // If members endpoint failed, try the invitations endpoint
@@ -121,15 +122,17 @@ impl<'a> UsersApi<'a> {
                     .send()
                     .await?;
 
-                if invitation_response.status().is_success() {
-                    debug!("Successfully added user to project using invitations endpoint");
-                    return Ok(());
-                }
-
+                let invitation_status = invitation_response.status();
                 let invitation_error = invitation_response
                     .text()
                     .await
                     .unwrap_or_else(|_| "Unknown error".to_string());
+
+                if invitation_status.is_success() && !Self::is_invitation_error(&invitation_error) {
+                    debug!("Successfully added user to project using invitations endpoint");
+                    return self.invitation_outcome(user_id, project_id).await;
+                }
+
                 anyhow::bail!(
                     "Failed to add user to project. Members endpoint error: {}. Invitations endpoint error: {}",
                     error_text,
@@ -165,20 +168,47 @@ impl<'a> UsersApi<'a> {
                     .send()
                     .await?;
 
-                if response.status().is_success() {
-                    debug!("Successfully added user to project using invitations endpoint");
-                    return Ok(());
-                }
-
+                let status = response.status();
                 let error_text = response
                     .text()
                     .await
                     .unwrap_or_else(|_| "Unknown error".to_string());
+
+                if status.is_success() && !Self::is_invitation_error(&error_text) {
+                    debug!("Successfully added user to project using invitations endpoint");
+                    return self.invitation_outcome(user_id, project_id).await;
+                }
+
                 anyhow::bail!("Failed to add user to project: {}", error_text);
             }
         }
     }
 
+    /// Inviting an existing user may add them as a member straight away; only report a
+    /// pending invitation when the user is not a member yet
+    async fn invitation_outcome(&self, user_id: u64, project_id: u64) -> Result<AddedAs> {
+        match self.get_project_member(user_id, project_id).await {
+            Ok(Some(_)) => Ok(AddedAs::Member),
+            Ok(None) => Ok(AddedAs::Invitation),
+            Err(e) => {
+                // The invitation itself succeeded, so don't fail the add over this lookup
+                warn!(
+                    "Could not check whether user {} joined project {} directly: {:#}",
+                    user_id, project_id, e
+                );
+                Ok(AddedAs::Invitation)
+            }
+        }
+    }
+
+    /// The invitations endpoint reports per-user failures with a success status
+    /// and `"status": "error"` in the body
+    fn is_invitation_error(body: &str) -> bool {
+        serde_json::from_str::<serde_json::Value>(body)
+            .map(|v| v.get("status").and_then(|s| s.as_str()) == Some("error"))
+            .unwrap_or(false)
+    }
+
     pub async fn remove_from_project(&self, user_id: u64, project_id: u64) -> Result<()> {
         let url = format!(
             "{}/projects/{}/members/{}",
@@ -198,4 +228,271 @@ impl<'a> UsersApi<'a> {
 
         Ok(())
     }
+
+    pub async fn get_memberships(&self, user_id: u64) -> Result<Vec<Membership>> {
+        let url = format!(
+            "{}/users/{}/memberships?per_page=100",
+            self.client.api_url(),
+            user_id
+        );
+
+        debug!("Fetching memberships for user {}", user_id);
+
+        let mut all_memberships = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let page_url = format!("{}&page={}", url, page);
+
+            let memberships: Vec<Membership> = self
+                .client
+                .http_client()
+                .get(&page_url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            if memberships.is_empty() {
+                break;
+            }
+
+            all_memberships.extend(memberships);
+            page += 1;
+        }
+
+        debug!(
+            "Found {} memberships for user {}",
+            all_memberships.len(),
+            user_id
+        );
+
+        Ok(all_memberships)
+    }
+
+    /// List the direct members of a project
+    pub async fn list_project_members(&self, project_id: u64) -> Result<Vec<Member>> {
+        let url = format!(
+            "{}/projects/{}/members?per_page=100",
+            self.client.api_url(),
+            project_id
+        );
+
+        debug!("Listing members of project {}", project_id);
+
+        self.fetch_members(&url).await
+    }
+
+    /// List the members of a project including those inherited from its groups
+    pub async fn list_all_project_members(&self, project_id: u64) -> Result<Vec<Member>> {
+        let url = format!(
+            "{}/projects/{}/members/all?per_page=100",
+            self.client.api_url(),
+            project_id
+        );
+
+        debug!("Listing all members of project {}", project_id);
+
+        self.fetch_members(&url).await
+    }
+
+    async fn fetch_members(&self, url: &str) -> Result<Vec<Member>> {
+        let mut all_members = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let page_url = format!("{}&page={}", url, page);
+
+            let members: Vec<Member> = self
+                .client
+                .http_client()
+                .get(&page_url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            if members.is_empty() {
+                break;
+            }
+
+            all_members.extend(members);
+            page += 1;
+        }
+
+        debug!("Found {} members", all_members.len());
+
+        Ok(all_members)
+    }
+
+    pub async fn get_project_member(
+        &self,
+        user_id: u64,
+        project_id: u64,
+    ) -> Result<Option<Member>> {
+        let url = format!(
+            "{}/projects/{}/members/{}",
+            self.client.api_url(),
+            project_id,
+            user_id
+        );
+
+        debug!(
+            "Fetching membership of user {} in project {}",
+            user_id, project_id
+        );
+
+        let response = self.client.http_client().get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let member = response.error_for_status()?.json::<Member>().await?;
+
+        Ok(Some(member))
+    }
+
+    /// Change the access level of an existing project member
+    pub async fn update_project_member(
+        &self,
+        user_id: u64,
+        project_id: u64,
+        access_level: AccessLevel,
+    ) -> Result<()> {
+        let url = format!(
+            "{}/projects/{}/members/{}",
+            self.client.api_url(),
+            project_id,
+            user_id
+        );
+
+        debug!(
+            "Updating user {} in project {} to access level {:?}",
+            user_id, project_id, access_level
+        );
+
+        let body = json!({
+            "access_level": access_level.as_u64(),
+        });
+
+        self.client
+            .http_client()
+            .put(&url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    pub async fn get_group_member(&self, user_id: u64, group_id: u64) -> Result<Option<Member>> {
+        let url = format!(
+            "{}/groups/{}/members/{}",
+            self.client.api_url(),
+            group_id,
+            user_id
+        );
+
+        debug!(
+            "Fetching membership of user {} in group {}",
+            user_id, group_id
+        );
+
+        let response = self.client.http_client().get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let member = response.error_for_status()?.json::<Member>().await?;
+
+        Ok(Some(member))
+    }
+
+    pub async fn add_to_group(
+        &self,
+        user_id: u64,
+        group_id: u64,
+        access_level: AccessLevel,
+    ) -> Result<()> {
+        let url = format!("{}/groups/{}/members", self.client.api_url(), group_id);
+
+        debug!(
+            "Adding user {} to group {} with access level {:?}",
+            user_id, group_id, access_level
+        );
+
+        let body = json!({
+            "user_id": user_id,
+            "access_level": access_level.as_u64(),
+        });
+
+        self.client
+            .http_client()
+            .post(&url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    /// Change the access level of an existing group member
+    pub async fn update_group_member(
+        &self,
+        user_id: u64,
+        group_id: u64,
+        access_level: AccessLevel,
+    ) -> Result<()> {
+        let url = format!(
+            "{}/groups/{}/members/{}",
+            self.client.api_url(),
+            group_id,
+            user_id
+        );
+
+        debug!(
+            "Updating user {} in group {} to access level {:?}",
+            user_id, group_id, access_level
+        );
+
+        let body = json!({
+            "access_level": access_level.as_u64(),
+        });
+
+        self.client
+            .http_client()
+            .put(&url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    pub async fn remove_from_group(&self, user_id: u64, group_id: u64) -> Result<()> {
+        let url = format!(
+            "{}/groups/{}/members/{}",
+            self.client.api_url(),
+            group_id,
+            user_id
+        );
+
+        debug!("Removing user {} from group {}", user_id, group_id);
+
+        self.client
+            .http_client()
+            .delete(&url)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
 }
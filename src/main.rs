@@ -3,14 +3,11 @@ use clap::{Parser, Subcommand};
 use tracing::{Level, info};
 use tracing_subscriber::FmtSubscriber;
 
-mod commands;
-mod gitlab;
-mod models;
-mod utils;
-
-use commands::{
-    file::FileCommands, projects::ProjectsCommands, topics::TopicsCommands, user::UserCommands,
+use gitlab_cli::commands::{
+    branches::BranchesCommands, file::FileCommands, invitations::InvitationsCommands,
+    policy::PolicyCommands, projects::ProjectsCommands, topics::TopicsCommands, user::UserCommands,
 };
+use gitlab_cli::gitlab;
 
 #[derive(Parser)]
 #[command(
@@ -52,6 +49,15 @@ enum Commands {
 
     /// Manage projects in GitLab
     Projects(ProjectsCommands),
+
+    /// Manage pending project invitations
+    Invitations(InvitationsCommands),
+
+    /// Manage branches in GitLab projects
+    Branches(BranchesCommands),
+
+    /// Check projects against a protected branch policy
+    Policy(PolicyCommands),
 }
 
 #[tokio::main]
@@ -94,6 +100,9 @@ async fn main() -> Result<()> {
         Commands::File(cmd) => cmd.execute(&client).await?,
         Commands::Topics(cmd) => cmd.execute(&client).await?,
         Commands::Projects(cmd) => cmd.execute(&client).await?,
+        Commands::Invitations(cmd) => cmd.execute(&client).await?,
+        Commands::Branches(cmd) => cmd.execute(&client).await?,
+        Commands::Policy(cmd) => cmd.execute(&client).await?,
     }
     info!("GitLab bulk management CLI completed successfully");
 
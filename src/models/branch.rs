@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::user::AccessLevel;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Branch {
+    pub name: String,
+    pub commit: BranchCommit,
+    #[serde(default)]
+    pub protected: bool,
+    #[serde(default)]
+    pub default: bool,
+    #[serde(default)]
+    pub web_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchCommit {
+    pub id: String,
+}
+
+/// A protected branch rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtectedBranch {
+    pub name: String,
+    #[serde(default)]
+    pub push_access_levels: Vec<BranchAccessLevel>,
+    #[serde(default)]
+    pub merge_access_levels: Vec<BranchAccessLevel>,
+    #[serde(default)]
+    pub unprotect_access_levels: Vec<BranchAccessLevel>,
+    #[serde(default)]
+    pub allow_force_push: bool,
+    #[serde(default)]
+    pub code_owner_approval_required: bool,
+}
+
+/// Who may push to, merge into or unprotect a protected branch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchAccessLevel {
+    /// ID of the entry, needed to remove it
+    #[serde(default)]
+    pub id: Option<u64>,
+    pub access_level: u64,
+    #[serde(default)]
+    pub access_level_description: String,
+    /// Set for entries granting access to a single user rather than a role
+    #[serde(default)]
+    pub user_id: Option<u64>,
+    /// Set for entries granting access to a group rather than a role
+    #[serde(default)]
+    pub group_id: Option<u64>,
+}
+
+impl BranchAccessLevel {
+    pub fn is_role(&self) -> bool {
+        self.user_id.is_none() && self.group_id.is_none()
+    }
+
+    /// The role-based level among `levels`, ignoring user and group entries
+    pub fn role_level(levels: &[Self]) -> Option<u64> {
+        levels.iter().find(|l| l.is_role()).map(|l| l.access_level)
+    }
+}
+
+/// An `allowed_to_*` entry granting a role, user or group access to a protected branch,
+/// or removing an existing entry
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AccessLevelEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_level: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<u64>,
+    #[serde(rename = "_destroy", skip_serializing_if = "std::ops::Not::not")]
+    pub destroy: bool,
+}
+
+impl AccessLevelEntry {
+    pub fn role(access_level: u64) -> Self {
+        Self {
+            access_level: Some(access_level),
+            ..Default::default()
+        }
+    }
+
+    pub fn destroy(id: u64) -> Self {
+        Self {
+            id: Some(id),
+            destroy: true,
+            ..Default::default()
+        }
+    }
+
+    /// The same grant as `level`, for another rule
+    pub fn copy_of(level: &BranchAccessLevel) -> Self {
+        match (level.user_id, level.group_id) {
+            (Some(user_id), _) => Self {
+                user_id: Some(user_id),
+                ..Default::default()
+            },
+            (None, Some(group_id)) => Self {
+                group_id: Some(group_id),
+                ..Default::default()
+            },
+            (None, None) => Self::role(level.access_level),
+        }
+    }
+}
+
+/// Parameters for protecting a branch; unset fields use GitLab's defaults
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NewProtectedBranch {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub push_access_level: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_access_level: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unprotect_access_level: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_force_push: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_owner_approval_required: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_to_push: Vec<AccessLevelEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_to_merge: Vec<AccessLevelEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_to_unprotect: Vec<AccessLevelEntry>,
+}
+
+/// Changes to an existing protected branch. They are applied in place with PATCH, so the
+/// branch stays protected and entries for specific users and groups are kept.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProtectedBranchUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_force_push: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_owner_approval_required: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_to_push: Vec<AccessLevelEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_to_merge: Vec<AccessLevelEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_to_unprotect: Vec<AccessLevelEntry>,
+}
+
+impl ProtectedBranchUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.allow_force_push.is_none()
+            && self.code_owner_approval_required.is_none()
+            && self.allowed_to_push.is_empty()
+            && self.allowed_to_merge.is_empty()
+            && self.allowed_to_unprotect.is_empty()
+    }
+}
+
+impl NewProtectedBranch {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// The same protection as `rule`, including its user and group entries, applied to
+    /// the branch `name`
+    pub fn copy_of(name: &str, rule: &ProtectedBranch) -> Self {
+        let copy =
+            |levels: &[BranchAccessLevel]| levels.iter().map(AccessLevelEntry::copy_of).collect();
+
+        Self {
+            name: name.to_string(),
+            allow_force_push: Some(rule.allow_force_push),
+            code_owner_approval_required: Some(rule.code_owner_approval_required),
+            allowed_to_push: copy(&rule.push_access_levels),
+            allowed_to_merge: copy(&rule.merge_access_levels),
+            allowed_to_unprotect: copy(&rule.unprotect_access_levels),
+            ..Default::default()
+        }
+    }
+
+    /// The changes that make `rule` match this protection. Only the role entries of a
+    /// differing access level are replaced; user and group entries are left alone.
+    pub fn update_for(&self, rule: &ProtectedBranch) -> ProtectedBranchUpdate {
+        let replace_role = |desired: Option<u64>, levels: &[BranchAccessLevel]| match desired {
+            Some(level) if BranchAccessLevel::role_level(levels) != Some(level) => levels
+                .iter()
+                .filter(|l| l.is_role())
+                .filter_map(|l| l.id)
+                .map(AccessLevelEntry::destroy)
+                .chain([AccessLevelEntry::role(level)])
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        ProtectedBranchUpdate {
+            allow_force_push: self
+                .allow_force_push
+                .filter(|&desired| desired != rule.allow_force_push),
+            code_owner_approval_required: self
+                .code_owner_approval_required
+                .filter(|&desired| desired != rule.code_owner_approval_required),
+            allowed_to_push: replace_role(self.push_access_level, &rule.push_access_levels),
+            allowed_to_merge: replace_role(self.merge_access_level, &rule.merge_access_levels),
+            allowed_to_unprotect: replace_role(
+                self.unprotect_access_level,
+                &rule.unprotect_access_levels,
+            ),
+        }
+    }
+
+    /// How `rule` differs from this protection, considering only the settings given here
+    pub fn differences(&self, rule: &ProtectedBranch) -> Vec<String> {
+        let describe = |level: Option<u64>| match level {
+            Some(level) => {
+                AccessLevel::from_u64(level).map_or_else(|| level.to_string(), |a| a.to_string())
+            }
+            None => "none".to_string(),
+        };
+
+        let mut differences = Vec::new();
+
+        for (label, desired, levels) in [
+            ("push", self.push_access_level, &rule.push_access_levels),
+            ("merge", self.merge_access_level, &rule.merge_access_levels),
+            (
+                "unprotect",
+                self.unprotect_access_level,
+                &rule.unprotect_access_levels,
+            ),
+        ] {
+            let actual = BranchAccessLevel::role_level(levels);
+            if desired.is_some() && desired != actual {
+                differences.push(format!(
+                    "{} access is {}, expected {}",
+                    label,
+                    describe(actual),
+                    describe(desired)
+                ));
+            }
+        }
+
+        for (label, desired, actual) in [
+            ("force push", self.allow_force_push, rule.allow_force_push),
+            (
+                "code owner approval",
+                self.code_owner_approval_required,
+                rule.code_owner_approval_required,
+            ),
+        ] {
+            if let Some(desired) = desired
+                && desired != actual
+            {
+                differences.push(format!(
+                    "{} is {}, expected {}",
+                    label,
+                    if actual { "on" } else { "off" },
+                    if desired { "on" } else { "off" }
+                ));
+            }
+        }
+
+        differences
+    }
+}
@@ -0,0 +1,158 @@
+use anyhow::Result;
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Commit {
+    pub id: String,
+    pub short_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub web_url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitActionKind {
+    Create,
+    Update,
+    Delete,
+    Move,
+    Chmod,
+}
+
+impl CommitActionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::Update => "update",
+            Self::Delete => "delete",
+            Self::Move => "move",
+            Self::Chmod => "chmod",
+        }
+    }
+}
+
+/// A single file change within a commit
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitAction {
+    pub action: CommitActionKind,
+    pub file_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execute_filemode: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_commit_id: Option<String>,
+}
+
+impl CommitAction {
+    pub fn new(action: CommitActionKind, file_path: &str) -> Self {
+        Self {
+            action,
+            file_path: file_path.to_string(),
+            previous_path: None,
+            content: None,
+            encoding: None,
+            execute_filemode: None,
+            last_commit_id: None,
+        }
+    }
+
+    pub fn with_content(mut self, content: &str) -> Self {
+        self.content = Some(content.to_string());
+        self
+    }
+
+    /// Set the content, base64-encoding it unless it is valid UTF-8
+    pub fn with_bytes(mut self, content: &[u8]) -> Self {
+        match std::str::from_utf8(content) {
+            Ok(text) => self.with_content(text),
+            Err(_) => {
+                self.content = Some(BASE64.encode(content));
+                self.encoding = Some("base64".to_string());
+                self
+            }
+        }
+    }
+
+    /// The decoded content of the action
+    pub fn content_bytes(&self) -> Result<Vec<u8>> {
+        let content = self.content.as_deref().unwrap_or_default();
+
+        match self.encoding.as_deref() {
+            Some("base64") => Ok(BASE64.decode(content)?),
+            _ => Ok(content.as_bytes().to_vec()),
+        }
+    }
+
+    pub fn with_previous_path(mut self, previous_path: &str) -> Self {
+        self.previous_path = Some(previous_path.to_string());
+        self
+    }
+
+    /// Make GitLab reject the action if the file changed after this commit
+    pub fn with_last_commit_id(mut self, last_commit_id: &str) -> Self {
+        self.last_commit_id = Some(last_commit_id.to_string());
+        self
+    }
+
+    pub fn with_execute_filemode(mut self, execute: bool) -> Self {
+        self.execute_filemode = Some(execute);
+        self
+    }
+}
+
+/// Message and author of a new commit
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CommitDetails {
+    pub commit_message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_email: Option<String>,
+}
+
+impl CommitDetails {
+    pub fn new(commit_message: &str) -> Self {
+        Self {
+            commit_message: commit_message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_author(mut self, name: Option<&str>, email: Option<&str>) -> Self {
+        self.author_name = name.map(str::to_string);
+        self.author_email = email.map(str::to_string);
+        self
+    }
+
+    /// Append `Key: value` trailers to the message, separated by a blank line
+    pub fn with_trailers(mut self, trailers: &[(String, String)]) -> Self {
+        if trailers.is_empty() {
+            return self;
+        }
+
+        let message = self.commit_message.trim_end();
+        let trailers = trailers
+            .iter()
+            .map(|(key, value)| format!("{}: {}", key, value))
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.commit_message = format!("{}\n\n{}", message, trailers);
+        self
+    }
+}
+
+/// Parameters for creating a commit with several actions
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NewCommit {
+    pub branch: String,
+    #[serde(flatten)]
+    pub details: CommitDetails,
+    pub actions: Vec<CommitAction>,
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Metadata of a repository file, as returned in the headers of the file HEAD endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileMetadata {
+    pub blob_id: String,
+    pub content_sha256: String,
+    pub last_commit_id: String,
+    pub size: u64,
+}
+
+/// A repository file with its decoded content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryFile {
+    pub file_path: String,
+    pub content: Vec<u8>,
+    pub metadata: FileMetadata,
+}
+
+impl RepositoryFile {
+    /// The content as text; `None` for binary files
+    pub fn text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.content).ok()
+    }
+}
+
+impl FileMetadata {
+    /// Whether the file in the repository already has exactly this content
+    pub fn matches(&self, content: &[u8]) -> bool {
+        self.content_sha256
+            .eq_ignore_ascii_case(&content_sha256(content))
+    }
+}
+
+/// Hex-encoded SHA-256 of file content, as GitLab reports it
+pub fn content_sha256(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// A write was rejected because the file changed on the branch after it was read
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct FileConflict(pub String);
+
+impl FileConflict {
+    /// Recognise GitLab's rejection of a stale `last_commit_id`
+    pub fn from_response(status: reqwest::StatusCode, body: &str) -> Option<Self> {
+        let stale = matches!(
+            status,
+            reqwest::StatusCode::BAD_REQUEST | reqwest::StatusCode::CONFLICT
+        ) && body.contains("changed since you started editing");
+
+        stale.then(|| {
+            let message = serde_json::from_str::<serde_json::Value>(body)
+                .ok()
+                .and_then(|v| {
+                    v.get("message")
+                        .and_then(|m| m.as_str())
+                        .map(str::to_string)
+                })
+                .unwrap_or_else(|| body.to_string());
+            Self(message)
+        })
+    }
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// A pending invitation to a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Invitation {
+    #[serde(default)]
+    pub invite_email: String,
+    pub access_level: u64,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    #[serde(default)]
+    pub user_name: Option<String>,
+    #[serde(default)]
+    pub created_by_name: Option<String>,
+}
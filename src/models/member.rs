@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// A user's membership of a project or group, as reported by `/users/:id/memberships`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Membership {
+    pub source_id: u64,
+    pub source_name: String,
+    /// Either "Project" or "Namespace" (group)
+    pub source_type: String,
+    pub access_level: u64,
+}
+
+impl Membership {
+    pub fn is_project(&self) -> bool {
+        self.source_type == "Project"
+    }
+}
+
+/// How a user ended up with access when added to a project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddedAs {
+    /// Added directly as a project member
+    Member,
+    /// Invited; access is pending until the invitation is accepted
+    Invitation,
+}
+
+/// A direct member of a project or group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub username: String,
+    pub name: String,
+    pub state: String,
+    pub access_level: u64,
+    #[serde(default)]
+    pub expires_at: Option<String>,
+}
+
+impl Member {
+    /// Whether this member can administer the project (Maintainer or Owner)
+    pub fn is_maintainer(&self) -> bool {
+        self.state == "active" && self.access_level >= 40
+    }
+}
+
+/// Whether removing `user_id` would leave no active Maintainer or Owner among `members`
+pub fn would_orphan(members: &[Member], user_id: u64) -> bool {
+    let removes_maintainer = members.iter().any(|m| m.id == user_id && m.is_maintainer());
+
+    removes_maintainer && !members.iter().any(|m| m.id != user_id && m.is_maintainer())
+}
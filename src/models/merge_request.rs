@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeRequest {
+    pub id: u64,
+    pub iid: u64,
+    pub title: String,
+    pub state: String,
+    pub source_branch: String,
+    pub target_branch: String,
+    pub web_url: String,
+}
+
+/// Parameters for opening a merge request
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NewMergeRequest {
+    pub source_branch: String,
+    pub target_branch: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Comma-separated list of labels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub assignee_ids: Vec<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reviewer_ids: Vec<u64>,
+    pub remove_source_branch: bool,
+}
@@ -1,2 +1,10 @@
+pub mod branch;
+pub mod commit;
+pub mod file;
+pub mod group;
+pub mod invitation;
+pub mod member;
+pub mod merge_request;
 pub mod project;
+pub mod search;
 pub mod user;
@@ -1,4 +1,6 @@
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
@@ -12,3 +14,96 @@ pub struct Project {
     #[serde(default)]
     pub topics: Vec<String>,
 }
+
+/// The values a project setting accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKind {
+    Bool,
+    Text,
+    OneOf(&'static [&'static str]),
+}
+
+/// A project setting that can be changed with `projects set`
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectSetting {
+    pub key: &'static str,
+    pub kind: SettingKind,
+}
+
+pub const PROJECT_SETTINGS: &[ProjectSetting] = &[
+    ProjectSetting {
+        key: "merge_method",
+        kind: SettingKind::OneOf(&["merge", "rebase_merge", "ff"]),
+    },
+    ProjectSetting {
+        key: "squash_option",
+        kind: SettingKind::OneOf(&["never", "always", "default_on", "default_off"]),
+    },
+    ProjectSetting {
+        key: "only_allow_merge_if_pipeline_succeeds",
+        kind: SettingKind::Bool,
+    },
+    ProjectSetting {
+        key: "remove_source_branch_after_merge",
+        kind: SettingKind::Bool,
+    },
+    ProjectSetting {
+        key: "visibility",
+        kind: SettingKind::OneOf(&["private", "internal", "public"]),
+    },
+    ProjectSetting {
+        key: "description",
+        kind: SettingKind::Text,
+    },
+    ProjectSetting {
+        key: "container_registry_access_level",
+        kind: SettingKind::OneOf(&["disabled", "private", "enabled"]),
+    },
+    ProjectSetting {
+        key: "lfs_enabled",
+        kind: SettingKind::Bool,
+    },
+];
+
+/// Parse and validate a `key=value` project setting
+pub fn parse_setting(raw: &str) -> Result<(String, Value)> {
+    let Some((key, value)) = raw.split_once('=') else {
+        anyhow::bail!("Expected key=value, got {:?}", raw);
+    };
+    let key = key.trim();
+
+    let Some(setting) = PROJECT_SETTINGS.iter().find(|s| s.key == key) else {
+        anyhow::bail!(
+            "Unknown project setting {:?}; supported settings: {}",
+            key,
+            PROJECT_SETTINGS
+                .iter()
+                .map(|s| s.key)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    };
+
+    let value = match setting.kind {
+        SettingKind::Bool => match value.trim() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            other => anyhow::bail!("{} must be true or false, got {:?}", key, other),
+        },
+        SettingKind::Text => Value::String(value.to_string()),
+        SettingKind::OneOf(allowed) => {
+            let value = value.trim();
+            if !allowed.contains(&value) {
+                anyhow::bail!(
+                    "{} must be one of {}, got {:?}",
+                    key,
+                    allowed.join(", "),
+                    value
+                );
+            }
+            Value::String(value.to_string())
+        }
+    };
+
+    Ok((key.to_string(), value))
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+/// A chunk of a file matching a blob search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlobMatch {
+    pub path: String,
+    pub data: String,
+    pub startline: u64,
+    pub project_id: u64,
+    #[serde(rename = "ref", default)]
+    pub git_ref: String,
+}
+
+/// A matching line, as reported by `file search`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchHit {
+    pub project: String,
+    pub path: String,
+    pub line: u64,
+    pub snippet: String,
+}
+
+impl BlobMatch {
+    /// The lines of the chunk containing `needle` (case-insensitively, like GitLab's search),
+    /// or the first line when the search matched on something else
+    pub fn matching_lines(&self, needle: &str) -> Vec<(u64, String)> {
+        let needle = needle.to_lowercase();
+        let lines = matching_lines(&self.data, self.startline, |line| {
+            line.to_lowercase().contains(&needle)
+        });
+
+        if lines.is_empty() {
+            let first = self.data.lines().next().unwrap_or_default();
+            return vec![(self.startline, first.trim().to_string())];
+        }
+
+        lines
+    }
+}
+
+/// Number and trimmed text of each line of `content` that matches, counting from `first_line`
+pub fn matching_lines(
+    content: &str,
+    first_line: u64,
+    matches: impl Fn(&str) -> bool,
+) -> Vec<(u64, String)> {
+    content
+        .lines()
+        .zip(first_line..)
+        .filter(|(line, _)| matches(line))
+        .map(|(line, number)| (number, line.trim().to_string()))
+        .collect()
+}
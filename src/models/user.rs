@@ -12,7 +12,7 @@ pub struct User {
     pub email: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub enum AccessLevel {
     NoAccess,
     MinimalAccess,
@@ -37,6 +37,20 @@ impl AccessLevel {
             Self::Owner => 50,
         }
     }
+
+    pub fn from_u64(level: u64) -> Option<Self> {
+        match level {
+            0 => Some(Self::NoAccess),
+            5 => Some(Self::MinimalAccess),
+            10 => Some(Self::Guest),
+            15 => Some(Self::Planner),
+            20 => Some(Self::Reporter),
+            30 => Some(Self::Developer),
+            40 => Some(Self::Maintainer),
+            50 => Some(Self::Owner),
+            _ => None,
+        }
+    }
 }
 
 impl FromStr for AccessLevel {
@@ -1,5 +1,6 @@
 use anyhow::{Context, Result};
-use csv::Reader;
+use csv::{Reader, Writer};
+use serde::Serialize;
 use std::fs::File;
 use std::path::Path;
 use tracing::debug;
@@ -95,6 +96,44 @@ impl CsvReader {
     }
 }
 
+#[derive(Debug)]
+pub struct CsvWriter {
+    path: std::path::PathBuf,
+}
+
+impl CsvWriter {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        debug!("Creating CSV writer for file: {:?}", path);
+        Self { path }
+    }
+
+    pub fn write_records<T: Serialize>(&self, records: &[T]) -> Result<()> {
+        debug!(
+            "Writing {} records to CSV file: {:?}",
+            records.len(),
+            self.path
+        );
+
+        let file = File::create(&self.path)
+            .with_context(|| format!("Failed to create CSV file: {:?}", self.path))?;
+
+        let mut writer = Writer::from_writer(file);
+
+        for record in records {
+            writer
+                .serialize(record)
+                .with_context(|| "Failed to write record to CSV")?;
+        }
+
+        writer
+            .flush()
+            .with_context(|| format!("Failed to write CSV file: {:?}", self.path))?;
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, serde::Deserialize)]
 struct UserRecord {
     id: u64,
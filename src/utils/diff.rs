@@ -0,0 +1,188 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use similar::TextDiff;
+
+/// Render a unified diff between two versions of a file; empty when they are identical
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(old_label, new_label)
+        .to_string()
+}
+
+/// Color a unified diff for terminal output
+pub fn colorize(diff: &str) -> String {
+    diff.lines()
+        .map(|line| {
+            if line.starts_with("+++") || line.starts_with("---") {
+                line.bold().to_string()
+            } else if line.starts_with('+') {
+                line.green().to_string()
+            } else if line.starts_with('-') {
+                line.red().to_string()
+            } else if line.starts_with("@@") {
+                line.cyan().to_string()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A hunk of a unified diff
+struct Hunk {
+    old_start: usize,
+    old: Vec<String>,
+    new: Vec<String>,
+}
+
+/// Apply a unified diff to `content`.
+///
+/// Hunks are located by their context and removed lines, so the patch still applies
+/// when the file has shifted since the diff was made.
+pub fn apply_patch(content: &str, patch: &str) -> Result<String> {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let mut output = String::new();
+    let mut position = 0;
+
+    for (index, hunk) in parse_hunks(patch)?.iter().enumerate() {
+        let start = find_hunk(&lines, position, hunk)
+            .ok_or_else(|| anyhow::anyhow!("Hunk {} of the patch does not apply", index + 1))?;
+
+        lines[position..start]
+            .iter()
+            .for_each(|line| output.push_str(line));
+        hunk.new.iter().for_each(|line| output.push_str(line));
+        position = start + hunk.old.len();
+    }
+
+    lines[position..]
+        .iter()
+        .for_each(|line| output.push_str(line));
+
+    Ok(output)
+}
+
+fn parse_hunks(patch: &str) -> Result<Vec<Hunk>> {
+    let mut hunks = Vec::new();
+    let mut files = 0;
+    let mut lines = patch.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        // Hunk bodies are consumed below, so file headers are only seen between hunks
+        if line.starts_with("+++ ") {
+            files += 1;
+            if files > 1 {
+                anyhow::bail!("Patch changes more than one file; pass a patch for a single file");
+            }
+            continue;
+        }
+
+        let Some(header) = line.strip_prefix("@@ -") else {
+            continue;
+        };
+
+        let (old_start, old_count, new_count) = parse_hunk_header(header)
+            .with_context(|| format!("Invalid hunk header in patch: {}", line))?;
+
+        let mut hunk = Hunk {
+            old_start,
+            old: Vec::new(),
+            new: Vec::new(),
+        };
+        let mut last_marker = ' ';
+
+        loop {
+            let complete = hunk.old.len() >= old_count && hunk.new.len() >= new_count;
+
+            // A missing final newline is marked right after the line it applies to
+            if lines.peek().is_some_and(|line| line.starts_with('\\')) {
+                lines.next();
+                if last_marker != '-'
+                    && let Some(last) = hunk.new.last_mut()
+                {
+                    last.pop();
+                }
+                continue;
+            }
+
+            if complete {
+                break;
+            }
+
+            let Some(line) = lines.next() else {
+                anyhow::bail!("Patch ends in the middle of a hunk");
+            };
+
+            let (marker, text) = match line.chars().next() {
+                Some(marker @ (' ' | '-' | '+')) => (marker, &line[1..]),
+                // Some tools strip the leading space of empty context lines
+                None => (' ', ""),
+                _ => anyhow::bail!("Unexpected line in patch hunk: {}", line),
+            };
+
+            let text = format!("{}\n", text);
+            if marker != '+' {
+                hunk.old.push(text.clone());
+            }
+            if marker != '-' {
+                hunk.new.push(text);
+            }
+            last_marker = marker;
+        }
+
+        hunks.push(hunk);
+    }
+
+    if hunks.is_empty() {
+        anyhow::bail!("Patch contains no hunks");
+    }
+
+    Ok(hunks)
+}
+
+/// Parse "a,b +c,d @@" into the old start line and the old and new line counts
+fn parse_hunk_header(header: &str) -> Option<(usize, usize, usize)> {
+    let (ranges, _) = header.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+
+    let parse_range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+
+    let (old_start, old_count) = parse_range(old)?;
+    let (_, new_count) = parse_range(new)?;
+
+    Some((old_start, old_count, new_count))
+}
+
+/// Find where the old lines of `hunk` start, preferring the position in its header
+fn find_hunk(lines: &[&str], position: usize, hunk: &Hunk) -> Option<usize> {
+    // Pure insertions have no lines to match; "-N,0" inserts after line N
+    if hunk.old.is_empty() {
+        return Some(hunk.old_start.clamp(position, lines.len()));
+    }
+
+    let matches_at = |start: usize| {
+        start + hunk.old.len() <= lines.len()
+            && hunk.old.iter().zip(&lines[start..]).all(|(old, line)| {
+                old.trim_end_matches(['\r', '\n']) == line.trim_end_matches(['\r', '\n'])
+            })
+    };
+
+    let expected = hunk.old_start.saturating_sub(1).max(position);
+    if matches_at(expected) {
+        return Some(expected);
+    }
+
+    (position..lines.len()).find(|&start| matches_at(start))
+}
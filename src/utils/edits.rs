@@ -0,0 +1,141 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use std::path::Path;
+use tracing::debug;
+
+/// A content edit applied to a file before it is uploaded
+#[derive(Debug, Clone)]
+pub enum Edit {
+    /// Replace every occurrence of a literal string
+    Literal { find: String, replace: String },
+    /// Replace every match of a regex; the replacement may use `$1`/`${name}` capture groups
+    Regex { pattern: Regex, replace: String },
+    /// Append a line unless the content already has an identical line
+    AppendLine(String),
+    /// Remove every line identical to the given one
+    RemoveLine(String),
+}
+
+impl Edit {
+    pub fn literal(find: &str, replace: &str) -> Result<Self> {
+        if find.is_empty() {
+            anyhow::bail!("The string to replace must not be empty");
+        }
+
+        Ok(Self::Literal {
+            find: find.to_string(),
+            replace: replace.to_string(),
+        })
+    }
+
+    pub fn regex(pattern: &str, replace: &str) -> Result<Self> {
+        let pattern = Regex::new(pattern).with_context(|| format!("Invalid regex: {}", pattern))?;
+
+        Ok(Self::Regex {
+            pattern,
+            replace: replace.to_string(),
+        })
+    }
+
+    pub fn apply(&self, content: &str) -> String {
+        match self {
+            Self::Literal { find, replace } => content.replace(find, replace),
+            Self::Regex { pattern, replace } => {
+                pattern.replace_all(content, replace.as_str()).into_owned()
+            }
+            Self::AppendLine(line) => {
+                if content.lines().any(|existing| existing == line) {
+                    return content.to_string();
+                }
+
+                let mut content = content.to_string();
+                if !content.is_empty() && !content.ends_with('\n') {
+                    content.push('\n');
+                }
+                content.push_str(line);
+                content.push('\n');
+                content
+            }
+            Self::RemoveLine(line) => content
+                .split_inclusive('\n')
+                .filter(|existing| existing.trim_end_matches(['\r', '\n']) != line)
+                .collect(),
+        }
+    }
+
+    /// Rewrite the text this edit inserts, e.g. to render template variables
+    pub fn map_replacement(&self, f: impl Fn(&str) -> Result<String>) -> Result<Self> {
+        Ok(match self {
+            Self::Literal { find, replace } => Self::Literal {
+                find: find.clone(),
+                replace: f(replace)?,
+            },
+            Self::Regex { pattern, replace } => Self::Regex {
+                pattern: pattern.clone(),
+                replace: f(replace)?,
+            },
+            Self::AppendLine(line) => Self::AppendLine(f(line)?),
+            Self::RemoveLine(line) => Self::RemoveLine(line.clone()),
+        })
+    }
+}
+
+/// Apply all edits in order
+pub fn apply_all(edits: &[Edit], content: &str) -> String {
+    edits
+        .iter()
+        .fold(content.to_string(), |content, edit| edit.apply(&content))
+}
+
+/// Build edits from pairs of values given on the command line
+pub fn from_pairs(
+    values: &[String],
+    build: impl Fn(&str, &str) -> Result<Edit>,
+) -> Result<Vec<Edit>> {
+    values
+        .chunks(2)
+        .map(|pair| match pair {
+            [find, replace] => build(find, replace),
+            _ => anyhow::bail!("Expected a search value and a replacement"),
+        })
+        .collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct EditsFile {
+    #[serde(default)]
+    edit: Vec<EditRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EditRecord {
+    find: Option<String>,
+    regex: Option<String>,
+    replace: String,
+}
+
+/// Load edits from a TOML file with `[[edit]]` entries, each having `replace`
+/// and either a literal `find` or a `regex`
+pub fn load_edits_file<P: AsRef<Path>>(path: P) -> Result<Vec<Edit>> {
+    let path = path.as_ref();
+    debug!("Loading edits from file: {:?}", path);
+
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read edits file: {:?}", path))?;
+
+    let file: EditsFile = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse edits file: {:?}", path))?;
+
+    file.edit
+        .iter()
+        .map(|record| match (&record.find, &record.regex) {
+            (Some(find), None) => Edit::literal(find, &record.replace),
+            (None, Some(regex)) => Edit::regex(regex, &record.replace),
+            _ => anyhow::bail!(
+                "Each edit in {:?} needs exactly one of `find` or `regex`",
+                path
+            ),
+        })
+        .collect()
+}
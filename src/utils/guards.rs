@@ -0,0 +1,69 @@
+use anyhow::{Context, Result};
+use regex::bytes::Regex;
+
+/// Conditions on a repository's current file that must hold for it to be written
+#[derive(Debug)]
+pub struct Guards {
+    only_if_exists: bool,
+    only_if_missing: bool,
+    if_contains: Option<Regex>,
+    if_not_contains: Option<Regex>,
+}
+
+impl Guards {
+    pub fn new(
+        only_if_exists: bool,
+        only_if_missing: bool,
+        if_contains: Option<&str>,
+        if_not_contains: Option<&str>,
+    ) -> Result<Self> {
+        let compile = |pattern: Option<&str>| -> Result<Option<Regex>> {
+            pattern
+                .map(|p| Regex::new(p).with_context(|| format!("Invalid regex: {}", p)))
+                .transpose()
+        };
+
+        Ok(Self {
+            only_if_exists,
+            only_if_missing,
+            if_contains: compile(if_contains)?,
+            if_not_contains: compile(if_not_contains)?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.only_if_exists && !self.only_if_missing && !self.needs_content()
+    }
+
+    pub fn needs_content(&self) -> bool {
+        self.if_contains.is_some() || self.if_not_contains.is_some()
+    }
+
+    /// Why the file must not be written, given its current content; `None` when it may be
+    pub fn check(&self, target_path: &str, current: Option<&[u8]>) -> Option<String> {
+        match current {
+            None if self.only_if_exists || self.if_contains.is_some() => {
+                Some(format!("{} does not exist", target_path))
+            }
+            Some(_) if self.only_if_missing => Some(format!("{} already exists", target_path)),
+            Some(content) => {
+                if let Some(re) = &self.if_contains
+                    && !re.is_match(content)
+                {
+                    return Some(format!(
+                        "{} does not contain /{}/",
+                        target_path,
+                        re.as_str()
+                    ));
+                }
+                if let Some(re) = &self.if_not_contains
+                    && re.is_match(content)
+                {
+                    return Some(format!("{} contains /{}/", target_path, re.as_str()));
+                }
+                None
+            }
+            None => None,
+        }
+    }
+}
@@ -1,2 +1,8 @@
 pub mod config;
 pub mod csv;
+pub mod diff;
+pub mod edits;
+pub mod guards;
+pub mod structured;
+pub mod template;
+pub mod text_style;
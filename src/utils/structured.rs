@@ -0,0 +1,445 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Format of a structured file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Yaml,
+    Json,
+    Toml,
+}
+
+impl Format {
+    /// Guess the format from the file extension
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = path.rsplit_once('.')?.1.to_ascii_lowercase();
+
+        match extension.as_str() {
+            "yml" | "yaml" => Some(Self::Yaml),
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// Split a dotted key path; `\.` stands for a literal dot inside a key
+pub fn parse_key_path(key: &str) -> Result<Vec<String>> {
+    let mut keys = Vec::new();
+    let mut current = String::new();
+    let mut chars = key.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.clone().next() == Some('.') => {
+                current.push('.');
+                chars.next();
+            }
+            '.' => keys.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    keys.push(current);
+
+    if keys.iter().any(|k| k.is_empty()) {
+        anyhow::bail!("Invalid key path: {:?}", key);
+    }
+
+    Ok(keys)
+}
+
+/// A value to set. Numbers keep the text they were given as, so `1.20` is not written
+/// back as `1.2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scalar {
+    Bool(bool),
+    Number(String),
+    String(String),
+}
+
+impl Scalar {
+    fn text(&self) -> String {
+        match self {
+            Self::Bool(b) => b.to_string(),
+            Self::Number(n) => n.clone(),
+            Self::String(s) => s.clone(),
+        }
+    }
+}
+
+/// Interpret a command line value as a boolean, number or string
+pub fn parse_value(raw: &str, force_string: bool) -> Scalar {
+    if !force_string {
+        match raw {
+            "true" => return Scalar::Bool(true),
+            "false" => return Scalar::Bool(false),
+            _ => {}
+        }
+        // Only JSON number syntax, which YAML and TOML read as numbers too
+        if serde_json::from_str::<serde_json::Number>(raw).is_ok() {
+            return Scalar::Number(raw.to_string());
+        }
+    }
+
+    Scalar::String(raw.to_string())
+}
+
+/// Set the value at `path`, creating missing parent mappings.
+///
+/// TOML and YAML files keep their comments and layout; JSON is re-serialized
+/// with its original indentation and key order.
+pub fn set_key(content: &str, format: Format, path: &[String], value: &Scalar) -> Result<String> {
+    match format {
+        Format::Json => set_json(content, path, value),
+        Format::Toml => set_toml(content, path, value),
+        Format::Yaml => set_yaml(content, path, value),
+    }
+}
+
+fn set_json(content: &str, path: &[String], value: &Scalar) -> Result<String> {
+    let mut document: Value = if content.trim().is_empty() {
+        Value::Object(Default::default())
+    } else {
+        serde_json::from_str(content).context("Failed to parse JSON")?
+    };
+
+    let mut current = &mut document;
+    for (depth, key) in path.iter().enumerate() {
+        let last = depth == path.len() - 1;
+
+        current = match current {
+            Value::Object(map) => map.entry(key.clone()).or_insert_with(|| {
+                if last {
+                    Value::Null
+                } else {
+                    Value::Object(Default::default())
+                }
+            }),
+            Value::Array(items) => {
+                let index: usize = key
+                    .parse()
+                    .ok()
+                    .filter(|&i| i < items.len())
+                    .with_context(|| format!("No array element {:?}", key))?;
+                &mut items[index]
+            }
+            _ => anyhow::bail!("{} is not an object", path[..depth].join(".")),
+        };
+    }
+    // A number replacing a string stays a string, like a quoted YAML value. Numbers are
+    // written as a placeholder and spliced in after serializing to keep their exact text.
+    let mut number = None;
+    *current = match value {
+        Scalar::Bool(b) => Value::Bool(*b),
+        Scalar::Number(n) if !current.is_string() => {
+            number = Some(n);
+            Value::String(NUMBER_PLACEHOLDER.to_string())
+        }
+        _ => Value::String(value.text()),
+    };
+
+    let indent = detect_indent(content).unwrap_or_else(|| "  ".to_string());
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+    let mut output = Vec::new();
+    document.serialize(&mut serde_json::Serializer::with_formatter(
+        &mut output,
+        formatter,
+    ))?;
+
+    let mut output = String::from_utf8(output)?;
+    if let Some(number) = number {
+        let placeholder = serde_json::to_string(NUMBER_PLACEHOLDER)?;
+        if output.matches(&placeholder).count() != 1 {
+            anyhow::bail!(
+                "Cannot set a number in a JSON file that contains {}",
+                placeholder
+            );
+        }
+        output = output.replacen(&placeholder, number, 1);
+    }
+    if content.ends_with('\n') || content.is_empty() {
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+/// Stands in for a number being set in a JSON document until it is serialized
+const NUMBER_PLACEHOLDER: &str = "\u{0}number\u{0}";
+
+fn set_toml(content: &str, path: &[String], value: &Scalar) -> Result<String> {
+    let mut document: toml_edit::DocumentMut = content.parse().context("Failed to parse TOML")?;
+
+    let (last, parents) = path.split_last().context("Empty key path")?;
+
+    let mut table: &mut dyn toml_edit::TableLike = document.as_table_mut();
+    for (depth, key) in parents.iter().enumerate() {
+        let item = table.entry(key).or_insert(toml_edit::table());
+        table = item
+            .as_table_like_mut()
+            .with_context(|| format!("{} is not a table", path[..=depth].join(".")))?;
+    }
+
+    let existing_is_string = table
+        .get(last)
+        .and_then(|item| item.as_value())
+        .is_some_and(|v| v.is_str());
+
+    let mut new_value = match value {
+        Scalar::Bool(b) => toml_edit::Value::from(*b),
+        // A number replacing a string stays a string, like a quoted YAML value
+        Scalar::Number(n) if existing_is_string => toml_edit::Value::from(n.as_str()),
+        Scalar::Number(n) => n
+            .parse::<toml_edit::Value>()
+            .with_context(|| format!("{} is not a valid TOML number", n))?,
+        Scalar::String(s) => toml_edit::Value::from(s.as_str()),
+    };
+
+    // Replace an existing value in place to keep its key, spacing and comments
+    match table.get_mut(last) {
+        Some(existing) => {
+            let existing = existing
+                .as_value_mut()
+                .with_context(|| format!("{} is a table, not a value", path.join(".")))?;
+            *new_value.decor_mut() = existing.decor().clone();
+            *existing = new_value;
+        }
+        None => {
+            table.insert(last, toml_edit::Item::Value(new_value));
+        }
+    }
+
+    Ok(document.to_string())
+}
+
+fn set_yaml(content: &str, path: &[String], value: &Scalar) -> Result<String> {
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let step = yaml_indent_step(&lines);
+
+    let mut start = 0;
+    let mut end = lines.len();
+    let mut parent_indent: Option<usize> = None;
+
+    for (depth, key) in path.iter().enumerate() {
+        let last = depth == path.len() - 1;
+        let first_child = (start..end).find(|&i| is_yaml_content(&lines[i]));
+
+        if let Some(i) = first_child
+            && lines[i].trim_start().starts_with('-')
+        {
+            anyhow::bail!(
+                "{} is a sequence, which is not supported",
+                path[..depth].join(".")
+            );
+        }
+
+        let child_indent = match first_child {
+            Some(i) => indent_of(&lines[i]),
+            None => parent_indent.map_or(0, |indent| indent + step),
+        };
+
+        let found = (start..end).find(|&i| {
+            is_yaml_content(&lines[i])
+                && indent_of(&lines[i]) == child_indent
+                && yaml_value_after_key(&lines[i], key).is_some()
+        });
+
+        let Some(i) = found else {
+            // Insert the missing keys after the last line of the parent mapping
+            let insert_at = (start..end)
+                .rev()
+                .find(|&i| is_yaml_content(&lines[i]))
+                .map_or(start, |i| i + 1);
+
+            let mut new_lines = Vec::new();
+            for (offset, key) in path[depth..].iter().enumerate() {
+                let indent = " ".repeat(child_indent + offset * step);
+                if depth + offset == path.len() - 1 {
+                    new_lines.push(format!(
+                        "{}{}: {}",
+                        indent,
+                        yaml_key(key),
+                        yaml_scalar(value, None)?
+                    ));
+                } else {
+                    new_lines.push(format!("{}{}:", indent, yaml_key(key)));
+                }
+            }
+            lines.splice(insert_at..insert_at, new_lines);
+            break;
+        };
+
+        let block_end = (i + 1..end)
+            .find(|&j| is_yaml_content(&lines[j]) && indent_of(&lines[j]) <= child_indent)
+            .unwrap_or(end);
+        let (value_start, rest) = yaml_value_after_key(&lines[i], key).unwrap_or_default();
+        let (current, comment) = split_yaml_comment(rest);
+
+        if last {
+            if current.is_empty() && block_end > i + 1 {
+                anyhow::bail!("{} is a mapping, not a scalar", path.join("."));
+            }
+            if current.starts_with(['|', '>', '&', '*', '{', '[']) {
+                anyhow::bail!(
+                    "{} has a value that cannot be replaced safely: {}",
+                    path.join("."),
+                    current
+                );
+            }
+
+            let quote = current.chars().next().filter(|c| *c == '"' || *c == '\'');
+            let line = &lines[i];
+            lines[i] = format!(
+                "{} {}{}",
+                &line[..value_start],
+                yaml_scalar(value, quote)?,
+                comment
+            );
+        } else {
+            if !current.is_empty() {
+                anyhow::bail!("{} is not a mapping", path[..=depth].join("."));
+            }
+            start = i + 1;
+            end = block_end;
+            parent_indent = Some(child_indent);
+        }
+    }
+
+    let mut output = lines.join(newline);
+    if content.ends_with('\n') || content.is_empty() {
+        output.push_str(newline);
+    }
+
+    Ok(output)
+}
+
+/// Leading whitespace of the first indented line
+fn detect_indent(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let indent: String = line
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect();
+        (!indent.is_empty()).then_some(indent)
+    })
+}
+
+fn yaml_indent_step(lines: &[String]) -> usize {
+    lines
+        .iter()
+        .filter(|line| is_yaml_content(line))
+        .map(|line| indent_of(line))
+        .filter(|&indent| indent > 0)
+        .min()
+        .unwrap_or(2)
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Lines other than blanks, comments and document markers
+fn is_yaml_content(line: &str) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty() && !trimmed.starts_with('#') && trimmed != "---" && trimmed != "..."
+}
+
+/// If the line is `key: value`, return the byte offset of the colon's end and the rest
+fn yaml_value_after_key<'a>(line: &'a str, key: &str) -> Option<(usize, &'a str)> {
+    let trimmed = line.trim_start();
+    let offset = line.len() - trimmed.len();
+
+    [
+        key.to_string(),
+        format!("\"{}\"", key),
+        format!("'{}'", key),
+    ]
+    .iter()
+    .find_map(|candidate| {
+        let rest = trimmed
+            .strip_prefix(candidate.as_str())?
+            .strip_prefix(':')?;
+        (rest.is_empty() || rest.starts_with([' ', '\t']))
+            .then(|| (offset + candidate.len() + 1, rest))
+    })
+}
+
+/// Split the text after a key into the value and a trailing comment (with its spacing)
+fn split_yaml_comment(rest: &str) -> (&str, &str) {
+    let value_start = rest.len() - rest.trim_start().len();
+    let value = &rest[value_start..];
+
+    let value_end = match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => {
+            let mut escaped = false;
+            value
+                .char_indices()
+                .skip(1)
+                .find(|&(_, c)| {
+                    let closes = c == quote && !escaped;
+                    escaped = quote == '"' && c == '\\' && !escaped;
+                    closes
+                })
+                .map_or(value.len(), |(i, _)| i + 1)
+        }
+        _ => value.find(" #").unwrap_or(value.len()),
+    };
+
+    let current = value[..value_end].trim_end();
+    let comment = &value[current.len()..];
+    (
+        current,
+        if comment.trim().is_empty() {
+            ""
+        } else {
+            comment
+        },
+    )
+}
+
+fn yaml_key(key: &str) -> String {
+    if needs_yaml_quotes(key) {
+        format!("\"{}\"", key.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        key.to_string()
+    }
+}
+
+/// Render a scalar, keeping the quote style of the value it replaces
+fn yaml_scalar(value: &Scalar, quote: Option<char>) -> Result<String> {
+    let text = value.text();
+
+    Ok(match (quote, value) {
+        (Some('\''), _) => format!("'{}'", text.replace('\'', "''")),
+        (Some(_), _) => serde_json::to_string(&text)?,
+        (None, Scalar::String(s)) if needs_yaml_quotes(s) => serde_json::to_string(s)?,
+        (None, _) => text,
+    })
+}
+
+/// Whether a plain scalar would be read back as something else or break the line
+fn needs_yaml_quotes(text: &str) -> bool {
+    let lower = text.to_ascii_lowercase();
+
+    text.is_empty()
+        || text.trim() != text
+        || matches!(
+            lower.as_str(),
+            "true" | "false" | "yes" | "no" | "on" | "off" | "null" | "~" | "y" | "n"
+        )
+        || text.parse::<f64>().is_ok()
+        || text.starts_with([
+            '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%',
+            '@', '`',
+        ])
+        || text.contains(": ")
+        || text.contains(" #")
+        || text.ends_with(':')
+}
@@ -0,0 +1,62 @@
+use std::sync::LazyLock;
+
+use anyhow::Result;
+use regex::{Captures, Regex};
+
+use crate::models::project::Project;
+
+static VARIABLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*([A-Za-z0-9_.-]+)\s*\}\}").unwrap());
+
+/// Render `{{project.*}}` and `{{topic.*}}` variables for a project.
+///
+/// Supported variables are `project.id`, `project.name`, `project.path`,
+/// `project.namespace`, `project.path_with_namespace`, `project.default_branch`
+/// and `topic.<key>`, which is the value of a `key:value` topic.
+pub fn render(template: &str, project: &Project) -> Result<String> {
+    let mut missing = Vec::new();
+
+    let rendered = VARIABLE.replace_all(template, |caps: &Captures| {
+        let name = &caps[1];
+        match lookup(name, project) {
+            Some(value) => value,
+            None => {
+                missing.push(name.to_string());
+                caps[0].to_string()
+            }
+        }
+    });
+
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "Unknown template variables for project {}: {}",
+            project.path_with_namespace,
+            missing.join(", ")
+        );
+    }
+
+    Ok(rendered.into_owned())
+}
+
+fn lookup(name: &str, project: &Project) -> Option<String> {
+    let (namespace, path) = project
+        .path_with_namespace
+        .rsplit_once('/')
+        .unwrap_or(("", &project.path_with_namespace));
+
+    match name {
+        "project.id" => Some(project.id.to_string()),
+        "project.name" => Some(project.name.clone()),
+        "project.path" => Some(path.to_string()),
+        "project.namespace" => Some(namespace.to_string()),
+        "project.path_with_namespace" => Some(project.path_with_namespace.clone()),
+        "project.default_branch" => project.default_branch.clone(),
+        _ => {
+            let key = name.strip_prefix("topic.")?;
+            project.topics.iter().find_map(|topic| {
+                let (topic_key, value) = topic.split_once(':')?;
+                (topic_key.trim() == key).then(|| value.trim().to_string())
+            })
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use anyhow::Result;
+
+const BOM: char = '\u{feff}';
+
+/// Line ending, byte order mark and final newline conventions of a text file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextStyle {
+    /// `Some(true)` for CRLF and `Some(false)` for LF; `None` when the file mixes both
+    /// or has no line breaks
+    pub crlf: Option<bool>,
+    /// Whether the file starts with a UTF-8 byte order mark
+    pub bom: bool,
+    /// Whether the last line ends with a line break; `None` for empty files
+    pub final_newline: Option<bool>,
+}
+
+impl TextStyle {
+    pub fn detect(text: &str) -> Self {
+        let crlf = text.matches("\r\n").count();
+        let lf = text.matches('\n').count() - crlf;
+        let body = text.strip_prefix(BOM).unwrap_or(text);
+
+        Self {
+            crlf: match (crlf, lf) {
+                (0, 0) => None,
+                (_, 0) => Some(true),
+                (0, _) => Some(false),
+                _ => None,
+            },
+            bom: text.starts_with(BOM),
+            final_newline: (!body.is_empty()).then(|| body.ends_with('\n')),
+        }
+    }
+
+    /// Rewrite `text` to follow this style
+    pub fn apply(&self, text: &str) -> String {
+        let body = text.strip_prefix(BOM).unwrap_or(text);
+
+        let mut body = match self.crlf {
+            Some(true) => body.replace("\r\n", "\n").replace('\n', "\r\n"),
+            Some(false) => body.replace("\r\n", "\n"),
+            None => body.to_string(),
+        };
+
+        match self.final_newline {
+            Some(true) if !body.is_empty() && !body.ends_with('\n') => {
+                body.push_str(if self.crlf == Some(true) {
+                    "\r\n"
+                } else {
+                    "\n"
+                });
+            }
+            Some(false) => {
+                let trimmed = body
+                    .strip_suffix("\r\n")
+                    .or_else(|| body.strip_suffix('\n'))
+                    .map(str::len);
+                if let Some(len) = trimmed {
+                    body.truncate(len);
+                }
+            }
+            _ => {}
+        }
+
+        if self.bom {
+            body.insert(0, BOM);
+        }
+
+        body
+    }
+}
+
+/// Give `content` the style of `original` when both are UTF-8 text; binary content is
+/// returned unchanged
+pub fn match_style(original: &[u8], content: Vec<u8>) -> Vec<u8> {
+    match (std::str::from_utf8(original), std::str::from_utf8(&content)) {
+        (Ok(original), Ok(text)) => TextStyle::detect(original).apply(text).into_bytes(),
+        _ => content,
+    }
+}
+
+/// Transform `original` without its byte order mark and give the result the style of
+/// `original`. With `enabled` false the transform sees and returns the text as is.
+pub fn preserve(
+    original: &str,
+    enabled: bool,
+    transform: impl FnOnce(&str) -> Result<String>,
+) -> Result<String> {
+    if !enabled {
+        return transform(original);
+    }
+
+    let style = TextStyle::detect(original);
+    let content = transform(original.strip_prefix(BOM).unwrap_or(original))?;
+
+    Ok(style.apply(&content))
+}
@@ -0,0 +1,261 @@
+use anyhow::Result;
+use gitlab_cli::gitlab::client::GitLabClient;
+use gitlab_cli::models::branch::{NewProtectedBranch, ProtectedBranch, ProtectedBranchUpdate};
+use gitlab_cli::models::user::AccessLevel;
+use mockito::Matcher;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_list_branches() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let _first_page = server
+        .mock("GET", "/projects/1/repository/branches")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("search".into(), "release".into()),
+            Matcher::UrlEncoded("page".into(), "1".into()),
+        ]))
+        .with_body(
+            json!([
+                {"name": "release/1.0", "commit": {"id": "7b5c3cc8be40ee161ae89a06bba6229da1032a0c"}, "protected": true},
+                {"name": "release/1.1", "commit": {"id": "c7d8a3e2"}}
+            ])
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let _last_page = server
+        .mock("GET", "/projects/1/repository/branches")
+        .match_query(Matcher::UrlEncoded("page".into(), "2".into()))
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let branches = client.branches().list(1, Some("release")).await?;
+
+    assert_eq!(branches.len(), 2);
+    assert_eq!(branches[0].name, "release/1.0");
+    assert!(branches[0].protected);
+    assert!(!branches[1].protected);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delete_branch() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("DELETE", "/projects/1/repository/branches/feature%2Fold")
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    client.branches().delete(1, "feature/old").await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_protect_and_unprotect_branch() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let protect = server
+        .mock("POST", "/projects/1/protected_branches")
+        .match_body(Matcher::Json(json!({"name": "release/*"})))
+        .with_status(201)
+        .with_body(
+            json!({
+                "id": 1,
+                "name": "release/*",
+                "push_access_levels": [{"access_level": 40, "access_level_description": "Maintainers"}],
+                "merge_access_levels": [{"access_level": 40, "access_level_description": "Maintainers"}],
+                "allow_force_push": false,
+                "code_owner_approval_required": false
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let unprotect = server
+        .mock("DELETE", "/projects/1/protected_branches/release%2F%2A")
+        .with_status(204)
+        .create_async()
+        .await;
+    let _missing = server
+        .mock("GET", "/projects/1/protected_branches/develop")
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+
+    let protected = client
+        .branches()
+        .protect(1, &NewProtectedBranch::new("release/*"))
+        .await?;
+    assert_eq!(protected.push_access_levels[0].access_level, 40);
+    assert!(!protected.allow_force_push);
+
+    client.branches().unprotect(1, "release/*").await?;
+    assert!(
+        client
+            .branches()
+            .get_protected(1, "develop")
+            .await?
+            .is_none()
+    );
+
+    protect.assert_async().await;
+    unprotect.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_copy_branch_protection() -> Result<()> {
+    let rule: ProtectedBranch = serde_json::from_value(json!({
+        "name": "master",
+        "push_access_levels": [{"access_level": 0, "access_level_description": "No one"}],
+        "merge_access_levels": [
+            {"access_level": 30, "access_level_description": "Developers + Maintainers"},
+            {"access_level": 40, "user_id": 7},
+            {"access_level": 30, "group_id": 9}
+        ],
+        "unprotect_access_levels": [],
+        "allow_force_push": false,
+        "code_owner_approval_required": true
+    }))?;
+
+    assert_eq!(
+        serde_json::to_value(NewProtectedBranch::copy_of("main", &rule))?,
+        json!({
+            "name": "main",
+            "allow_force_push": false,
+            "code_owner_approval_required": true,
+            "allowed_to_push": [{"access_level": 0}],
+            "allowed_to_merge": [
+                {"access_level": 30},
+                {"user_id": 7},
+                {"group_id": 9}
+            ]
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_protection_differences() -> Result<()> {
+    let rule: ProtectedBranch = serde_json::from_value(json!({
+        "name": "main",
+        "push_access_levels": [
+            {"access_level": 40, "user_id": 7},
+            {"access_level": 30}
+        ],
+        "merge_access_levels": [{"access_level": 40}],
+        "allow_force_push": true,
+        "code_owner_approval_required": false
+    }))?;
+
+    let policy = NewProtectedBranch {
+        push_access_level: Some(AccessLevel::NoAccess.as_u64()),
+        merge_access_level: Some(AccessLevel::Maintainer.as_u64()),
+        allow_force_push: Some(false),
+        ..NewProtectedBranch::new("main")
+    };
+
+    assert_eq!(
+        policy.differences(&rule),
+        vec![
+            "push access is Developer, expected No Access",
+            "force push is on, expected off",
+        ]
+    );
+
+    // Settings left out of the policy are not checked
+    assert!(
+        NewProtectedBranch::new("main")
+            .differences(&rule)
+            .is_empty()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_protection_update_keeps_user_and_group_entries() -> Result<()> {
+    let rule: ProtectedBranch = serde_json::from_value(json!({
+        "name": "main",
+        "push_access_levels": [
+            {"id": 1, "access_level": 30},
+            {"id": 2, "access_level": 40, "user_id": 7},
+            {"id": 3, "access_level": 30, "group_id": 9}
+        ],
+        "merge_access_levels": [{"id": 4, "access_level": 40}],
+        "allow_force_push": true,
+        "code_owner_approval_required": false
+    }))?;
+
+    let policy = NewProtectedBranch {
+        push_access_level: Some(AccessLevel::Maintainer.as_u64()),
+        merge_access_level: Some(AccessLevel::Maintainer.as_u64()),
+        allow_force_push: Some(false),
+        ..NewProtectedBranch::new("main")
+    };
+
+    // Only the differing role entry is replaced; entries 2 and 3 are not touched
+    assert_eq!(
+        serde_json::to_value(policy.update_for(&rule))?,
+        json!({
+            "allow_force_push": false,
+            "allowed_to_push": [
+                {"id": 1, "_destroy": true},
+                {"access_level": 40}
+            ]
+        })
+    );
+
+    assert!(NewProtectedBranch::new("main").update_for(&rule).is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_protected_branch() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("PATCH", "/projects/1/protected_branches/release%2F%2A")
+        .match_body(Matcher::Json(json!({"code_owner_approval_required": true})))
+        .with_body(
+            json!({
+                "name": "release/*",
+                "push_access_levels": [{"id": 2, "access_level": 40, "user_id": 7}],
+                "code_owner_approval_required": true
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let update = ProtectedBranchUpdate {
+        code_owner_approval_required: Some(true),
+        ..Default::default()
+    };
+    let rule = client
+        .branches()
+        .update_protected(1, "release/*", &update)
+        .await?;
+
+    assert!(rule.code_owner_approval_required);
+    assert_eq!(rule.push_access_levels[0].user_id, Some(7));
+    mock.assert_async().await;
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use anyhow::Result;
+use gitlab_cli::gitlab::client::GitLabClient;
+use gitlab_cli::models::commit::{CommitAction, CommitActionKind, CommitDetails, NewCommit};
+use mockito::Matcher;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_create_commit_with_multiple_actions() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/projects/1/repository/commits")
+        .match_body(Matcher::Json(json!({
+            "branch": "main",
+            "commit_message": "Update build files",
+            "actions": [
+                {"action": "update", "file_path": "Dockerfile", "content": "FROM python:3.12"},
+                {"action": "create", "file_path": ".gitlab-ci.yml", "content": "stages: [test]"},
+                {"action": "delete", "file_path": ".travis.yml"},
+                {"action": "move", "file_path": "docs/README.md", "previous_path": "README.md"},
+                {"action": "chmod", "file_path": "build.sh", "execute_filemode": true}
+            ]
+        })))
+        .with_status(201)
+        .with_body(
+            json!({
+                "id": "ed899a2f4b50b4370feeea94676502b42383c746",
+                "short_id": "ed899a2f",
+                "title": "Update build files",
+                "web_url": "https://gitlab.example.com/group/project/-/commit/ed899a2f"
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let commit = client
+        .commits()
+        .create(
+            1,
+            &NewCommit {
+                branch: "main".to_string(),
+                details: CommitDetails::new("Update build files"),
+                actions: vec![
+                    CommitAction::new(CommitActionKind::Update, "Dockerfile")
+                        .with_content("FROM python:3.12"),
+                    CommitAction::new(CommitActionKind::Create, ".gitlab-ci.yml")
+                        .with_content("stages: [test]"),
+                    CommitAction::new(CommitActionKind::Delete, ".travis.yml"),
+                    CommitAction::new(CommitActionKind::Move, "docs/README.md")
+                        .with_previous_path("README.md"),
+                    CommitAction::new(CommitActionKind::Chmod, "build.sh")
+                        .with_execute_filemode(true),
+                ],
+            },
+        )
+        .await?;
+
+    mock.assert_async().await;
+    assert_eq!(commit.short_id, "ed899a2f");
+
+    Ok(())
+}
+
+#[test]
+fn test_commit_action_binary_content() -> Result<()> {
+    let binary = [0x89, b'P', b'N', b'G', 0xff, 0x00];
+
+    let text = CommitAction::new(CommitActionKind::Create, "README.md").with_bytes(b"# Title");
+    let image = CommitAction::new(CommitActionKind::Create, "logo.png").with_bytes(&binary);
+
+    assert_eq!(text.content.as_deref(), Some("# Title"));
+    assert_eq!(text.encoding, None);
+    assert_eq!(image.content.as_deref(), Some("iVBOR/8A"));
+    assert_eq!(image.encoding.as_deref(), Some("base64"));
+    assert_eq!(image.content_bytes()?, binary);
+
+    Ok(())
+}
+
+#[test]
+fn test_commit_details_author_and_trailers() -> Result<()> {
+    let details = CommitDetails::new("Bump Python to 3.12\n")
+        .with_author(Some("Release Bot"), Some("release-bot@example.com"))
+        .with_trailers(&[
+            ("Refs".to_string(), "PLAT-123".to_string()),
+            ("Change-Id".to_string(), "I0123abcd".to_string()),
+        ]);
+
+    assert_eq!(
+        serde_json::to_value(&details)?,
+        serde_json::json!({
+            "commit_message": "Bump Python to 3.12\n\nRefs: PLAT-123\nChange-Id: I0123abcd",
+            "author_name": "Release Bot",
+            "author_email": "release-bot@example.com"
+        })
+    );
+
+    let plain = CommitDetails::new("Update file").with_trailers(&[]);
+    assert_eq!(
+        serde_json::to_value(&plain)?,
+        serde_json::json!({ "commit_message": "Update file" })
+    );
+
+    Ok(())
+}
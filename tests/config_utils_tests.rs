@@ -62,4 +62,3 @@ fn test_config_serialization() -> Result<()> {
 
     Ok(())
 }
-
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use gitlab_cli::utils::csv::CsvReader;
+use gitlab_cli::utils::csv::{CsvReader, CsvWriter};
 use std::fs::File;
 use std::io::Write;
 use tempfile::tempdir;
@@ -101,3 +101,42 @@ fn test_read_users_from_invalid_file() {
     let err = result.unwrap_err().to_string();
     assert!(err.contains("nonexistent.csv"));
 }
+
+#[test]
+fn test_write_records_to_csv() -> Result<()> {
+    #[derive(serde::Serialize)]
+    struct Record {
+        project: String,
+        status: String,
+    }
+
+    let dir = tempdir()?;
+    let file_path = dir.path().join("audit.csv");
+
+    let records = vec![
+        Record {
+            project: "group/project-a".to_string(),
+            status: "ok".to_string(),
+        },
+        Record {
+            project: "group/project-b".to_string(),
+            status: "failed".to_string(),
+        },
+    ];
+
+    CsvWriter::new(&file_path).write_records(&records)?;
+
+    let content = std::fs::read_to_string(&file_path)?;
+    let lines: Vec<&str> = content.lines().collect();
+
+    assert_eq!(
+        lines,
+        vec![
+            "project,status",
+            "group/project-a,ok",
+            "group/project-b,failed"
+        ]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use gitlab_cli::utils::diff::{apply_patch, unified_diff};
+
+#[test]
+fn test_unified_diff_identical() {
+    let content = "FROM python:3.12\nEXPOSE 8080\n";
+
+    assert!(unified_diff(content, content, "a/Dockerfile", "b/Dockerfile").is_empty());
+}
+
+#[test]
+fn test_unified_diff_changes() {
+    let old = "FROM python:3.8\nWORKDIR /app\nEXPOSE 8000\n";
+    let new = "FROM python:3.12\nWORKDIR /app\nEXPOSE 8000\n";
+
+    let diff = unified_diff(old, new, "a/Dockerfile", "b/Dockerfile");
+
+    assert!(diff.starts_with("--- a/Dockerfile\n+++ b/Dockerfile\n"));
+    assert!(diff.contains("@@ -1,3 +1,3 @@"));
+    assert!(diff.contains("-FROM python:3.8\n"));
+    assert!(diff.contains("+FROM python:3.12\n"));
+    assert!(diff.contains(" WORKDIR /app\n"));
+}
+
+#[test]
+fn test_unified_diff_new_file() {
+    let diff = unified_diff("", "line one\n", "a/NOTES", "b/NOTES");
+
+    assert!(diff.contains("+line one"));
+}
+
+#[test]
+fn test_apply_patch_with_shifted_lines() {
+    let patch = "--- a/Dockerfile\n+++ b/Dockerfile\n@@ -1,3 +1,3 @@\n-FROM python:3.8\n+FROM python:3.12\n WORKDIR /app\n EXPOSE 8000\n";
+    let content = "# syntax=docker/dockerfile:1\nFROM python:3.8\nWORKDIR /app\nEXPOSE 8000\n";
+
+    assert_eq!(
+        apply_patch(content, patch).unwrap(),
+        "# syntax=docker/dockerfile:1\nFROM python:3.12\nWORKDIR /app\nEXPOSE 8000\n"
+    );
+}
+
+#[test]
+fn test_apply_patch_roundtrip_without_final_newline() {
+    let old = "one\ntwo\nthree";
+    let new = "one\n2\nthree\nfour";
+    let patch = unified_diff(old, new, "a/f", "b/f");
+
+    assert_eq!(apply_patch(old, &patch).unwrap(), new);
+}
+
+#[test]
+fn test_apply_patch_rejects_mismatch() {
+    let patch = "@@ -1 +1 @@\n-FROM node:18\n+FROM node:20\n";
+
+    assert!(apply_patch("FROM python:3.8\n", patch).is_err());
+    assert!(apply_patch("FROM python:3.8\n", "not a patch").is_err());
+}
+
+#[test]
+fn test_apply_patch_rejects_multiple_files() {
+    let patch = "\
+--- a/Dockerfile
++++ b/Dockerfile
+@@ -1 +1 @@
+-FROM python:3.8
++FROM python:3.12
+--- a/ci/Dockerfile
++++ b/ci/Dockerfile
+@@ -1 +1 @@
+-FROM python:3.8
++FROM python:3.11
+";
+
+    let error = apply_patch("FROM python:3.8\n", patch).unwrap_err();
+    assert!(error.to_string().contains("more than one file"));
+
+    // A single file's headers are fine
+    let single = patch.split("--- a/ci").next().unwrap();
+    assert_eq!(
+        apply_patch("FROM python:3.8\n", single).unwrap(),
+        "FROM python:3.12\n"
+    );
+}
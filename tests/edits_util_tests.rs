@@ -0,0 +1,132 @@
+use gitlab_cli::models::project::Project;
+use gitlab_cli::utils::edits::{self, Edit};
+use gitlab_cli::utils::template;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+fn project(topics: &[&str]) -> Project {
+    Project {
+        id: 42,
+        path_with_namespace: "platform/services/billing".to_string(),
+        name: "Billing".to_string(),
+        description: None,
+        default_branch: Some("develop".to_string()),
+        visibility: "private".to_string(),
+        web_url: "https://gitlab.example.com/platform/services/billing".to_string(),
+        topics: topics.iter().map(|t| t.to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_literal_edit_with_colons() {
+    let edit = Edit::literal("FROM python:3.8", "FROM python:3.12").unwrap();
+
+    assert_eq!(
+        edit.apply("FROM python:3.8\nRUN echo python:3.8\n"),
+        "FROM python:3.12\nRUN echo python:3.8\n"
+    );
+}
+
+#[test]
+fn test_regex_edit_with_capture_groups() {
+    let edit = Edit::regex(r"image: (\S+):3\.8", "image: ${1}:3.12").unwrap();
+
+    assert_eq!(
+        edit.apply("image: python:3.8\nimage: pypy:3.8\n"),
+        "image: python:3.12\nimage: pypy:3.12\n"
+    );
+}
+
+#[test]
+fn test_invalid_edits() {
+    assert!(Edit::regex("(unclosed", "x").is_err());
+    assert!(Edit::literal("", "x").is_err());
+}
+
+#[test]
+fn test_from_pairs_and_apply_all() {
+    let values = vec![
+        "8000".to_string(),
+        "8080".to_string(),
+        "EXPOSE 8080".to_string(),
+        "EXPOSE 9090".to_string(),
+    ];
+    let edits = edits::from_pairs(&values, Edit::literal).unwrap();
+
+    assert_eq!(edits.len(), 2);
+    assert_eq!(edits::apply_all(&edits, "EXPOSE 8000\n"), "EXPOSE 9090\n");
+}
+
+#[test]
+fn test_load_edits_file() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(
+        file,
+        r#"
+[[edit]]
+find = "python:3.8"
+replace = "python:3.12"
+
+[[edit]]
+regex = 'EXPOSE (\d+)'
+replace = "EXPOSE 1$1"
+"#
+    )
+    .unwrap();
+
+    let edits = edits::load_edits_file(file.path()).unwrap();
+
+    assert_eq!(
+        edits::apply_all(&edits, "FROM python:3.8\nEXPOSE 8000\n"),
+        "FROM python:3.12\nEXPOSE 18000\n"
+    );
+}
+
+#[test]
+fn test_load_edits_file_requires_find_or_regex() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "[[edit]]\nreplace = \"x\"").unwrap();
+
+    assert!(edits::load_edits_file(file.path()).is_err());
+}
+
+#[test]
+fn test_render_project_variables() {
+    let rendered = template::render(
+        "key={{project.path}}\nns={{ project.namespace }}\nbranch={{project.default_branch}}\nteam={{topic.team}}\n",
+        &project(&["team:payments", "services"]),
+    )
+    .unwrap();
+
+    assert_eq!(
+        rendered,
+        "key=billing\nns=platform/services\nbranch=develop\nteam=payments\n"
+    );
+}
+
+#[test]
+fn test_render_unknown_variable_fails() {
+    let err = template::render("{{topic.team}} {{project.owner}}", &project(&[])).unwrap_err();
+
+    assert!(err.to_string().contains("topic.team, project.owner"));
+}
+
+#[test]
+fn test_line_edits() {
+    let append = Edit::AppendLine(".env".to_string());
+    let remove = Edit::RemoveLine("*.pyc".to_string());
+
+    assert_eq!(append.apply("target/"), "target/\n.env\n");
+    assert_eq!(append.apply("target/\n.env\n"), "target/\n.env\n");
+    assert_eq!(remove.apply("*.pyc\r\ntarget/\r\n*.pyc\r\n"), "target/\r\n");
+}
+
+#[test]
+fn test_map_replacement_renders_only_inserted_text() {
+    let edit = Edit::literal("{{project.path}}", "{{project.path}}-svc")
+        .unwrap()
+        .map_replacement(|text| template::render(text, &project(&[])))
+        .unwrap();
+
+    assert_eq!(edit.apply("name: {{project.path}}"), "name: billing-svc");
+}
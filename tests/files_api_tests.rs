@@ -0,0 +1,324 @@
+use anyhow::Result;
+use gitlab_cli::gitlab::client::GitLabClient;
+use gitlab_cli::models::commit::CommitDetails;
+use gitlab_cli::models::file::{FileConflict, content_sha256};
+use mockito::Matcher;
+use serde_json::json;
+
+const PNG_HEADER: &[u8] = &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0xff, 0x00];
+
+#[tokio::test]
+async fn test_get_file_metadata() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+    let content = b"FROM python:3.12\n";
+
+    let _mock = server
+        .mock("HEAD", "/projects/1/repository/files/docker%2FDockerfile")
+        .match_query(mockito::Matcher::UrlEncoded("ref".into(), "main".into()))
+        .with_header(
+            "X-Gitlab-Blob-Id",
+            "79f7bbd25901e8334750839545a9bd021f0e4c83",
+        )
+        .with_header("X-Gitlab-Content-Sha256", &content_sha256(content))
+        .with_header(
+            "X-Gitlab-Last-Commit-Id",
+            "570e7b2abdd848b95f2f578043fc23bd6f6fd24d",
+        )
+        .with_header("X-Gitlab-Size", "17")
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let metadata = client
+        .files()
+        .get_file_metadata(1, "docker/Dockerfile", "main")
+        .await?
+        .expect("file should exist");
+
+    assert_eq!(metadata.size, 17);
+    assert_eq!(
+        metadata.last_commit_id,
+        "570e7b2abdd848b95f2f578043fc23bd6f6fd24d"
+    );
+    assert!(metadata.matches(content));
+    assert!(!metadata.matches(b"FROM python:3.8\n"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_file_metadata_missing_file() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let _mock = server
+        .mock("HEAD", "/projects/1/repository/files/Dockerfile")
+        .match_query(mockito::Matcher::Any)
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let metadata = client
+        .files()
+        .get_file_metadata(1, "Dockerfile", "main")
+        .await?;
+
+    assert!(metadata.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_content_sha256() {
+    assert_eq!(
+        content_sha256(b"hello"),
+        "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+    );
+}
+
+#[tokio::test]
+async fn test_get_binary_file() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let _mock = server
+        .mock("GET", "/projects/1/repository/files/logo.png")
+        .match_query(Matcher::UrlEncoded("ref".into(), "main".into()))
+        .with_body(
+            json!({
+                "file_path": "logo.png",
+                "content": "iVBORw0KGgr/AA==",
+                "encoding": "base64",
+                "blob_id": "79f7bbd25901e8334750839545a9bd021f0e4c83",
+                "last_commit_id": "570e7b2abdd848b95f2f578043fc23bd6f6fd24d"
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let file = client
+        .files()
+        .get_file(1, "logo.png", "main")
+        .await?
+        .expect("file should exist");
+
+    assert_eq!(file.content, PNG_HEADER);
+    assert!(file.text().is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_file_encodes_ref() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/projects/1/repository/files/README.md")
+        .match_query(Matcher::UrlEncoded("ref".into(), "feat/a+b".into()))
+        .with_body(
+            json!({
+                "file_path": "README.md",
+                "content": "aGVsbG8K",
+                "encoding": "base64",
+                "blob_id": "79f7bbd25901e8334750839545a9bd021f0e4c83",
+                "last_commit_id": "570e7b2abdd848b95f2f578043fc23bd6f6fd24d"
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let file = client
+        .files()
+        .get_file(1, "README.md", "feat/a+b")
+        .await?
+        .expect("file should exist");
+
+    mock.assert_async().await;
+    assert_eq!(file.text(), Some("hello\n"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_binary_file() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/projects/1/repository/files/assets%2Flogo.png")
+        .match_body(Matcher::Json(json!({
+            "branch": "main",
+            "content": "iVBORw0KGgr/AA==",
+            "encoding": "base64",
+            "commit_message": "Add logo"
+        })))
+        .with_status(201)
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    client
+        .files()
+        .create_file(
+            1,
+            "assets/logo.png",
+            "main",
+            &CommitDetails::new("Add logo"),
+            PNG_HEADER,
+        )
+        .await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delete_file() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("DELETE", "/projects/1/repository/files/.travis.yml")
+        .match_body(Matcher::Json(json!({
+            "branch": "main",
+            "commit_message": "Remove Travis CI configuration"
+        })))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    client
+        .files()
+        .delete_file(
+            1,
+            ".travis.yml",
+            "main",
+            &CommitDetails::new("Remove Travis CI configuration"),
+            None,
+        )
+        .await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_raw_file() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let _found = server
+        .mock("GET", "/projects/1/repository/files/certs%2Fca.der/raw")
+        .match_query(Matcher::UrlEncoded("ref".into(), "main".into()))
+        .with_body(PNG_HEADER)
+        .create_async()
+        .await;
+    let _missing = server
+        .mock("GET", "/projects/2/repository/files/certs%2Fca.der/raw")
+        .match_query(Matcher::Any)
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+
+    assert_eq!(
+        client
+            .files()
+            .get_raw_file(1, "certs/ca.der", "main")
+            .await?,
+        Some(PNG_HEADER.to_vec())
+    );
+    assert_eq!(
+        client
+            .files()
+            .get_raw_file(2, "certs/ca.der", "main")
+            .await?,
+        None
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_file_sends_last_commit_id() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("PUT", "/projects/1/repository/files/Dockerfile")
+        .match_body(Matcher::Json(json!({
+            "branch": "main",
+            "content": "RlJPTSBweXRob246My4xMgo=",
+            "encoding": "base64",
+            "commit_message": "Bump Python",
+            "last_commit_id": "7d3b1a6c"
+        })))
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    client
+        .files()
+        .update_file(
+            1,
+            "Dockerfile",
+            "main",
+            &CommitDetails::new("Bump Python"),
+            b"FROM python:3.12\n",
+            Some("7d3b1a6c"),
+        )
+        .await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_file_conflict() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let _mock = server
+        .mock("PUT", "/projects/1/repository/files/Dockerfile")
+        .with_status(400)
+        .with_body(
+            r#"{"message":"You are attempting to update a file that has changed since you started editing it."}"#,
+        )
+        .create_async()
+        .await;
+    let _other = server
+        .mock("PUT", "/projects/2/repository/files/Dockerfile")
+        .with_status(400)
+        .with_body(r#"{"message":"A file with this name doesn't exist"}"#)
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let commit = CommitDetails::new("Bump Python");
+
+    let error = client
+        .files()
+        .update_file(1, "Dockerfile", "main", &commit, b"", Some("7d3b1a6c"))
+        .await
+        .unwrap_err();
+    let conflict = error.downcast_ref::<FileConflict>().expect("a conflict");
+    assert!(
+        conflict
+            .0
+            .starts_with("You are attempting to update a file")
+    );
+
+    let error = client
+        .files()
+        .update_file(2, "Dockerfile", "main", &commit, b"", None)
+        .await
+        .unwrap_err();
+    assert!(error.downcast_ref::<FileConflict>().is_none());
+    assert!(error.to_string().contains("doesn't exist"));
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use gitlab_cli::utils::guards::Guards;
+
+#[test]
+fn test_no_guards() {
+    let guards = Guards::new(false, false, None, None).unwrap();
+
+    assert!(guards.is_empty());
+    assert!(!guards.needs_content());
+    assert_eq!(guards.check("a.txt", None), None);
+    assert_eq!(guards.check("a.txt", Some(b"x")), None);
+}
+
+#[test]
+fn test_existence_guards() {
+    let only_if_exists = Guards::new(true, false, None, None).unwrap();
+    assert_eq!(
+        only_if_exists.check("a.txt", None),
+        Some("a.txt does not exist".to_string())
+    );
+    assert_eq!(only_if_exists.check("a.txt", Some(b"x")), None);
+
+    let only_if_missing = Guards::new(false, true, None, None).unwrap();
+    assert_eq!(only_if_missing.check("a.txt", None), None);
+    assert_eq!(
+        only_if_missing.check("a.txt", Some(b"x")),
+        Some("a.txt already exists".to_string())
+    );
+}
+
+#[test]
+fn test_content_guards() {
+    let if_contains = Guards::new(false, false, Some("python:3\\.8"), None).unwrap();
+    assert!(if_contains.needs_content());
+    assert_eq!(
+        if_contains.check("Dockerfile", None),
+        Some("Dockerfile does not exist".to_string())
+    );
+    assert_eq!(
+        if_contains.check("Dockerfile", Some(b"FROM python:3.12")),
+        Some("Dockerfile does not contain /python:3\\.8/".to_string())
+    );
+    assert_eq!(
+        if_contains.check("Dockerfile", Some(b"FROM python:3.8")),
+        None
+    );
+
+    // A missing file does not contain the pattern, so it may be created
+    let if_not_contains = Guards::new(false, false, None, Some("^\\.env$")).unwrap();
+    assert_eq!(if_not_contains.check(".gitignore", None), None);
+    assert_eq!(if_not_contains.check(".gitignore", Some(b"target\n")), None);
+    assert!(
+        Guards::new(false, false, None, Some("(?m)^\\.env$"))
+            .unwrap()
+            .check(".gitignore", Some(b"target\n.env\n"))
+            .is_some()
+    );
+}
+
+#[test]
+fn test_combined_guards() {
+    // Only update existing files that do not have the line yet
+    let guards = Guards::new(true, false, None, Some("(?m)^\\.env$")).unwrap();
+
+    assert_eq!(
+        guards.check(".gitignore", None),
+        Some(".gitignore does not exist".to_string())
+    );
+    assert_eq!(guards.check(".gitignore", Some(b"target\n")), None);
+    assert_eq!(
+        guards.check(".gitignore", Some(b".env\n")),
+        Some(".gitignore contains /(?m)^\\.env$/".to_string())
+    );
+}
+
+#[test]
+fn test_invalid_regex() {
+    assert!(Guards::new(false, false, Some("("), None).is_err());
+}
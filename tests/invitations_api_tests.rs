@@ -0,0 +1,88 @@
+use anyhow::Result;
+use gitlab_cli::gitlab::client::GitLabClient;
+use gitlab_cli::models::user::AccessLevel;
+use mockito::Matcher;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_list_invitations() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let _first_page = server
+        .mock("GET", "/projects/1/invitations")
+        .match_query(Matcher::UrlEncoded("page".into(), "1".into()))
+        .with_body(
+            json!([{
+                "invite_email": "new.hire@example.com",
+                "access_level": 30,
+                "created_at": "2024-01-01T00:00:00Z",
+                "expires_at": null
+            }])
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let _second_page = server
+        .mock("GET", "/projects/1/invitations")
+        .match_query(Matcher::UrlEncoded("page".into(), "2".into()))
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let invitations = client.invitations().list(1).await?;
+
+    assert_eq!(invitations.len(), 1);
+    assert_eq!(invitations[0].invite_email, "new.hire@example.com");
+    assert_eq!(invitations[0].access_level, 30);
+    assert!(invitations[0].expires_at.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_revoke_invitation_encodes_email() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("DELETE", "/projects/1/invitations/new.hire%40example.com")
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    client
+        .invitations()
+        .revoke(1, "new.hire@example.com")
+        .await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_invitation_reports_body_errors() {
+    let mut server = mockito::Server::new_async().await;
+
+    let _mock = server
+        .mock("POST", "/projects/1/invitations")
+        .with_status(201)
+        .with_body(
+            json!({
+                "status": "error",
+                "message": {"new.hire@example.com": "Invite email has already been taken"}
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let result = client
+        .invitations()
+        .create(1, "new.hire@example.com", AccessLevel::Developer, None)
+        .await;
+
+    assert!(result.is_err());
+}
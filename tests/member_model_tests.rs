@@ -0,0 +1,40 @@
+use gitlab_cli::models::member::{Member, would_orphan};
+
+fn member(id: u64, access_level: u64, state: &str) -> Member {
+    Member {
+        id,
+        username: format!("user{}", id),
+        name: format!("User {}", id),
+        state: state.to_string(),
+        access_level,
+        expires_at: None,
+    }
+}
+
+#[test]
+fn test_would_orphan_last_maintainer() {
+    let members = vec![member(1, 40, "active"), member(2, 30, "active")];
+
+    assert!(would_orphan(&members, 1));
+}
+
+#[test]
+fn test_would_not_orphan_with_other_owner() {
+    let members = vec![member(1, 40, "active"), member(2, 50, "active")];
+
+    assert!(!would_orphan(&members, 1));
+}
+
+#[test]
+fn test_would_not_orphan_when_removing_developer() {
+    let members = vec![member(1, 40, "active"), member(2, 30, "active")];
+
+    assert!(!would_orphan(&members, 2));
+}
+
+#[test]
+fn test_blocked_maintainers_do_not_count() {
+    let members = vec![member(1, 40, "active"), member(2, 40, "blocked")];
+
+    assert!(would_orphan(&members, 1));
+}
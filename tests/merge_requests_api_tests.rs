@@ -0,0 +1,210 @@
+use anyhow::Result;
+use gitlab_cli::gitlab::client::GitLabClient;
+use gitlab_cli::models::merge_request::NewMergeRequest;
+use mockito::Matcher;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_create_branch_and_merge_request() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let missing_branch = server
+        .mock("GET", "/projects/1/repository/branches/feature%2Fupdate")
+        .with_status(404)
+        .create_async()
+        .await;
+    let create_branch = server
+        .mock("POST", "/projects/1/repository/branches")
+        .match_body(Matcher::Json(
+            json!({"branch": "feature/update", "ref": "main"}),
+        ))
+        .with_status(201)
+        .with_body(json!({"name": "feature/update", "commit": {"id": "abc123"}}).to_string())
+        .create_async()
+        .await;
+    let create_mr = server
+        .mock("POST", "/projects/1/merge_requests")
+        .match_body(Matcher::Json(json!({
+            "source_branch": "feature/update",
+            "target_branch": "main",
+            "title": "Update CI",
+            "labels": "ci,bulk",
+            "reviewer_ids": [7],
+            "remove_source_branch": true
+        })))
+        .with_status(201)
+        .with_body(
+            json!({
+                "id": 100,
+                "iid": 3,
+                "title": "Update CI",
+                "state": "opened",
+                "source_branch": "feature/update",
+                "target_branch": "main",
+                "web_url": "https://gitlab.example.com/group/project/-/merge_requests/3"
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+
+    assert!(client.branches().get(1, "feature/update").await?.is_none());
+    let branch = client
+        .branches()
+        .create(1, "feature/update", "main")
+        .await?;
+    assert_eq!(branch.commit.id, "abc123");
+
+    let merge_request = client
+        .merge_requests()
+        .create(
+            1,
+            &NewMergeRequest {
+                source_branch: "feature/update".to_string(),
+                target_branch: "main".to_string(),
+                title: "Update CI".to_string(),
+                labels: Some("ci,bulk".to_string()),
+                reviewer_ids: vec![7],
+                remove_source_branch: true,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    missing_branch.assert_async().await;
+    create_branch.assert_async().await;
+    create_mr.assert_async().await;
+    assert_eq!(merge_request.iid, 3);
+    assert!(merge_request.web_url.ends_with("/merge_requests/3"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_merge_when_pipeline_succeeds() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("PUT", "/projects/1/merge_requests/3/merge")
+        .match_body(Matcher::Json(json!({"merge_when_pipeline_succeeds": true})))
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    client
+        .merge_requests()
+        .merge_when_pipeline_succeeds(1, 3)
+        .await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_open_and_retarget_merge_requests() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let merge_request = json!({
+        "id": 101,
+        "iid": 3,
+        "title": "Add feature",
+        "state": "opened",
+        "source_branch": "feature/x",
+        "target_branch": "master",
+        "web_url": "https://gitlab.example.com/group/project/-/merge_requests/3"
+    });
+
+    let _first_page = server
+        .mock("GET", "/projects/1/merge_requests")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("state".into(), "opened".into()),
+            Matcher::UrlEncoded("target_branch".into(), "master".into()),
+            Matcher::UrlEncoded("page".into(), "1".into()),
+        ]))
+        .with_body(json!([merge_request]).to_string())
+        .create_async()
+        .await;
+    let _last_page = server
+        .mock("GET", "/projects/1/merge_requests")
+        .match_query(Matcher::UrlEncoded("page".into(), "2".into()))
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let mut retargeted = merge_request.clone();
+    retargeted["target_branch"] = json!("main");
+    let retarget = server
+        .mock("PUT", "/projects/1/merge_requests/3")
+        .match_body(Matcher::Json(json!({"target_branch": "main"})))
+        .with_body(retargeted.to_string())
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+
+    let open = client.merge_requests().list_open(1, "master").await?;
+    assert_eq!(open.len(), 1);
+
+    let updated = client
+        .merge_requests()
+        .set_target_branch(1, open[0].iid, "main")
+        .await?;
+    assert_eq!(updated.target_branch, "main");
+
+    retarget.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_find_open_from_source_branch() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let _open = server
+        .mock("GET", "/projects/1/merge_requests")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("state".into(), "opened".into()),
+            Matcher::UrlEncoded("source_branch".into(), "bulk/update".into()),
+        ]))
+        .with_body(
+            json!([{
+                "id": 30,
+                "iid": 3,
+                "title": "Update config",
+                "state": "opened",
+                "source_branch": "bulk/update",
+                "target_branch": "main",
+                "web_url": "https://gitlab.example.com/group/project/-/merge_requests/3"
+            }])
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let _none = server
+        .mock("GET", "/projects/2/merge_requests")
+        .match_query(Matcher::Any)
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+
+    let found = client
+        .merge_requests()
+        .find_open_from(1, "bulk/update")
+        .await?;
+    assert_eq!(found.map(|mr| mr.iid), Some(3));
+
+    let missing = client
+        .merge_requests()
+        .find_open_from(2, "bulk/update")
+        .await?;
+    assert!(missing.is_none());
+
+    Ok(())
+}
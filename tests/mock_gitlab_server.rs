@@ -3,7 +3,6 @@ use gitlab_cli::models::{project::Project, user::User};
 use serde_json::json;
 use std::net::SocketAddr;
 use std::sync::{Arc, Mutex};
-use tracing::debug;
 use warp::{Filter, reply};
 
 /// A mock GitLab server for testing API interactions
@@ -39,7 +38,6 @@ impl MockGitLabServer {
     pub async fn start(&mut self) -> Result<()> {
         let users = self.users.clone();
         let projects_for_get = self.projects.clone();
-        let projects_for_topic = self.projects.clone();
 
         // Projects API
         let get_project = warp::path!("projects" / u64)
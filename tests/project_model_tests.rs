@@ -0,0 +1,39 @@
+use gitlab_cli::models::project::parse_setting;
+use serde_json::json;
+
+#[test]
+fn test_parse_setting() -> anyhow::Result<()> {
+    assert_eq!(
+        parse_setting("merge_method=ff")?,
+        ("merge_method".to_string(), json!("ff"))
+    );
+    assert_eq!(
+        parse_setting("lfs_enabled=false")?,
+        ("lfs_enabled".to_string(), json!(false))
+    );
+    // Text values are kept as given, including '=' and surrounding spaces
+    assert_eq!(
+        parse_setting("description= Billing API (a=b)")?,
+        ("description".to_string(), json!(" Billing API (a=b)"))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_setting_rejects_invalid_values() {
+    let error = parse_setting("squash_option=sometimes").unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("never, always, default_on, default_off")
+    );
+
+    let error = parse_setting("only_allow_merge_if_pipeline_succeeds=yes").unwrap_err();
+    assert!(error.to_string().contains("true or false"));
+
+    let error = parse_setting("wiki_enabled=true").unwrap_err();
+    assert!(error.to_string().contains("Unknown project setting"));
+
+    assert!(parse_setting("visibility").is_err());
+}
@@ -0,0 +1,212 @@
+use anyhow::Result;
+use gitlab_cli::gitlab::client::GitLabClient;
+use gitlab_cli::models::user::AccessLevel;
+use mockito::Matcher;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_share_with_group() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/projects/42/share")
+        .match_body(Matcher::Json(json!({
+            "group_id": 7,
+            "group_access": 30,
+            "expires_at": "2030-01-01"
+        })))
+        .with_status(201)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    client
+        .projects()
+        .share_with_group(42, 7, AccessLevel::Developer, Some("2030-01-01"))
+        .await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unshare_with_group() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("DELETE", "/projects/42/share/7")
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    client.projects().unshare_with_group(42, 7).await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_share_with_group_error() {
+    let mut server = mockito::Server::new_async().await;
+
+    let _mock = server
+        .mock("POST", "/projects/42/share")
+        .with_status(409)
+        .with_body(r#"{"message":"The project is already shared with this group"}"#)
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let result = client
+        .projects()
+        .share_with_group(42, 7, AccessLevel::Developer, None)
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_set_default_branch() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("PUT", "/projects/42")
+        .match_body(Matcher::Json(json!({"default_branch": "main"})))
+        .with_body(
+            json!({
+                "id": 42,
+                "path_with_namespace": "group/project",
+                "name": "project",
+                "description": null,
+                "default_branch": "main",
+                "visibility": "private",
+                "web_url": "https://gitlab.example.com/group/project"
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let project = client.projects().set_default_branch(42, "main").await?;
+
+    assert_eq!(project.default_branch.as_deref(), Some("main"));
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_settings() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("PUT", "/projects/42")
+        .match_body(Matcher::Json(json!({
+            "merge_method": "ff",
+            "remove_source_branch_after_merge": true
+        })))
+        .with_body(
+            json!({
+                "id": 42,
+                "merge_method": "ff",
+                "remove_source_branch_after_merge": true
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+
+    let mut settings = serde_json::Map::new();
+    settings.insert("merge_method".to_string(), json!("ff"));
+    settings.insert("remove_source_branch_after_merge".to_string(), json!(true));
+    let project = client.projects().update_settings(42, &settings).await?;
+
+    assert_eq!(project["merge_method"], "ff");
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_archive_and_unarchive() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let archive = server
+        .mock("POST", "/projects/42/archive")
+        .with_status(201)
+        .with_body(json!({"id": 42, "archived": true}).to_string())
+        .create_async()
+        .await;
+    let unarchive = server
+        .mock("POST", "/projects/42/unarchive")
+        .with_status(201)
+        .with_body(json!({"id": 42, "archived": false}).to_string())
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    client.projects().archive(42).await?;
+    client.projects().unarchive(42).await?;
+
+    archive.assert_async().await;
+    unarchive.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transfer() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("PUT", "/projects/42/transfer")
+        .match_body(Matcher::Json(json!({"namespace": 7})))
+        .with_body(
+            json!({
+                "id": 42,
+                "path_with_namespace": "archive/project",
+                "name": "project",
+                "description": null,
+                "default_branch": "main",
+                "visibility": "private",
+                "web_url": "https://gitlab.example.com/archive/project"
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let project = client.projects().transfer(42, 7).await?;
+
+    assert_eq!(project.path_with_namespace, "archive/project");
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delete() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("DELETE", "/projects/42")
+        .with_status(202)
+        .with_body(json!({"message": "202 Accepted"}).to_string())
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    client.projects().delete(42).await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use anyhow::Result;
+use gitlab_cli::gitlab::client::GitLabClient;
+use gitlab_cli::models::search::{BlobMatch, matching_lines};
+use mockito::Matcher;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_project_blob_search() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let first_page = server
+        .mock("GET", "/projects/1/search")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("scope".into(), "blobs".into()),
+            Matcher::UrlEncoded("search".into(), "python:3.8".into()),
+            Matcher::UrlEncoded("ref".into(), "develop".into()),
+            Matcher::UrlEncoded("page".into(), "1".into()),
+        ]))
+        .with_body(
+            json!([{
+                "basename": "Dockerfile",
+                "data": "# build image\nFROM python:3.8\nWORKDIR /app\n",
+                "path": "Dockerfile",
+                "filename": "Dockerfile",
+                "id": null,
+                "ref": "develop",
+                "startline": 4,
+                "project_id": 1
+            }])
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let _last_page = server
+        .mock("GET", "/projects/1/search")
+        .match_query(Matcher::UrlEncoded("page".into(), "2".into()))
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let blobs = client
+        .search()
+        .project_blobs(1, "python:3.8", Some("develop"))
+        .await?;
+
+    first_page.assert_async().await;
+    assert_eq!(blobs.len(), 1);
+    assert_eq!(
+        blobs[0].matching_lines("PYTHON:3.8"),
+        vec![(5, "FROM python:3.8".to_string())]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_group_blob_search_unavailable() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let _mock = server
+        .mock("GET", "/groups/7/search")
+        .match_query(Matcher::Any)
+        .with_status(400)
+        .with_body(json!({"error": "Scope supported only with advanced search"}).to_string())
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+
+    assert!(client.search().group_blobs(7, "python").await.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_matching_lines() {
+    let content = "FROM python:3.8\nRUN pip install -r requirements.txt\n  ENV PYTHON=3.8\n";
+
+    assert_eq!(
+        matching_lines(content, 1, |line| line.contains("3.8")),
+        vec![
+            (1, "FROM python:3.8".to_string()),
+            (3, "ENV PYTHON=3.8".to_string())
+        ]
+    );
+}
+
+#[test]
+fn test_blob_match_without_literal_match_reports_first_line() {
+    let blob = BlobMatch {
+        path: "setup.py".to_string(),
+        data: "  install_requires=[\n    'requests',\n".to_string(),
+        startline: 10,
+        project_id: 1,
+        git_ref: "main".to_string(),
+    };
+
+    assert_eq!(
+        blob.matching_lines("install requires"),
+        vec![(10, "install_requires=[".to_string())]
+    );
+}
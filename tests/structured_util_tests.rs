@@ -0,0 +1,151 @@
+use gitlab_cli::utils::structured::{Format, Scalar, parse_key_path, parse_value, set_key};
+
+fn set(content: &str, format: Format, key: &str, value: &str) -> String {
+    set_key(
+        content,
+        format,
+        &parse_key_path(key).unwrap(),
+        &parse_value(value, false),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_format_from_path() {
+    assert_eq!(Format::from_path(".gitlab-ci.yml"), Some(Format::Yaml));
+    assert_eq!(Format::from_path("package.json"), Some(Format::Json));
+    assert_eq!(Format::from_path("pyproject.TOML"), Some(Format::Toml));
+    assert_eq!(Format::from_path("Dockerfile"), None);
+}
+
+#[test]
+fn test_parse_key_path_and_value() {
+    assert_eq!(
+        parse_key_path(r"\.base.image").unwrap(),
+        vec![".base", "image"]
+    );
+    assert!(parse_key_path("variables..X").is_err());
+
+    assert_eq!(parse_value("24", false), Scalar::Number("24".into()));
+    assert_eq!(parse_value("1.20", false), Scalar::Number("1.20".into()));
+    assert_eq!(parse_value("true", false), Scalar::Bool(true));
+    assert_eq!(parse_value("24", true), Scalar::String("24".into()));
+    assert_eq!(
+        parse_value("python:3.12", false),
+        Scalar::String("python:3.12".into())
+    );
+    assert_eq!(parse_value("+5", false), Scalar::String("+5".into()));
+}
+
+#[test]
+fn test_set_yaml_existing_value_keeps_quotes_and_comments() {
+    let content = "# CI\nvariables:\n  DOCKER_VERSION: \"20\"  # pinned\n  OTHER: x\n\nbuild:\n  script: make\n";
+
+    assert_eq!(
+        set(content, Format::Yaml, "variables.DOCKER_VERSION", "24"),
+        "# CI\nvariables:\n  DOCKER_VERSION: \"24\"  # pinned\n  OTHER: x\n\nbuild:\n  script: make\n"
+    );
+}
+
+#[test]
+fn test_set_yaml_inserts_missing_keys() {
+    let content = "variables:\n    OTHER: x\n\nbuild:\n    script: make\n";
+
+    assert_eq!(
+        set(content, Format::Yaml, "variables.DOCKER_VERSION", "24"),
+        "variables:\n    OTHER: x\n    DOCKER_VERSION: 24\n\nbuild:\n    script: make\n"
+    );
+    assert_eq!(
+        set(content, Format::Yaml, "default.image", "python:3.12"),
+        "variables:\n    OTHER: x\n\nbuild:\n    script: make\ndefault:\n    image: python:3.12\n"
+    );
+}
+
+#[test]
+fn test_set_yaml_rejects_non_scalars() {
+    let content = "build:\n  script:\n    - make\n";
+    let path = parse_key_path("build.script.x").unwrap();
+
+    assert!(set_key(content, Format::Yaml, &path, &Scalar::String("y".into())).is_err());
+    assert!(
+        set_key(
+            content,
+            Format::Yaml,
+            &parse_key_path("build").unwrap(),
+            &Scalar::String("y".into())
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_set_json_keeps_order_and_indent() {
+    let content =
+        "{\n    \"name\": \"app\",\n    \"engines\": {\n        \"node\": \">=18\"\n    }\n}\n";
+
+    assert_eq!(
+        set(content, Format::Json, "engines.node", ">=20"),
+        "{\n    \"name\": \"app\",\n    \"engines\": {\n        \"node\": \">=20\"\n    }\n}\n"
+    );
+}
+
+#[test]
+fn test_set_toml_keeps_comments() {
+    let content = "[tool.black]\n# formatter\nline-length = 88 # default\n";
+
+    assert_eq!(
+        set(content, Format::Toml, "tool.black.line-length", "100"),
+        "[tool.black]\n# formatter\nline-length = 100 # default\n"
+    );
+    assert_eq!(
+        set(content, Format::Toml, "project.name", "app"),
+        "[tool.black]\n# formatter\nline-length = 88 # default\n\n[project]\nname = \"app\"\n"
+    );
+}
+
+#[test]
+fn test_set_keeps_number_text() {
+    let yaml = "go: 1.19\nquoted: \"1.19\"\n";
+    assert_eq!(
+        set(
+            &set(yaml, Format::Yaml, "go", "1.20"),
+            Format::Yaml,
+            "quoted",
+            "1.20"
+        ),
+        "go: 1.20\nquoted: \"1.20\"\n"
+    );
+    assert_eq!(
+        set(yaml, Format::Yaml, "node", "24.0"),
+        "go: 1.19\nquoted: \"1.19\"\nnode: 24.0\n"
+    );
+
+    let json = "{\n  \"go\": 1.19,\n  \"quoted\": \"1.19\"\n}\n";
+    assert_eq!(
+        set(json, Format::Json, "go", "1.20"),
+        "{\n  \"go\": 1.20,\n  \"quoted\": \"1.19\"\n}\n"
+    );
+    assert_eq!(
+        set(json, Format::Json, "quoted", "1.20"),
+        "{\n  \"go\": 1.19,\n  \"quoted\": \"1.20\"\n}\n"
+    );
+    assert_eq!(
+        set(json, Format::Json, "node", "24.0"),
+        "{\n  \"go\": 1.19,\n  \"quoted\": \"1.19\",\n  \"node\": 24.0\n}\n"
+    );
+
+    let toml = "go = 1.19\nquoted = \"1.19\"\n";
+    assert_eq!(
+        set(
+            &set(toml, Format::Toml, "go", "1.20"),
+            Format::Toml,
+            "quoted",
+            "1.20"
+        ),
+        "go = 1.20\nquoted = \"1.20\"\n"
+    );
+    assert_eq!(
+        set(toml, Format::Toml, "node", "24.0"),
+        "go = 1.19\nquoted = \"1.19\"\nnode = 24.0\n"
+    );
+}
@@ -0,0 +1,58 @@
+use gitlab_cli::utils::text_style::{self, TextStyle};
+
+#[test]
+fn test_detect_style() {
+    let style = TextStyle::detect("\u{feff}FROM python:3.8\r\nRUN make\r\n");
+    assert_eq!(style.crlf, Some(true));
+    assert!(style.bom);
+    assert_eq!(style.final_newline, Some(true));
+
+    let style = TextStyle::detect("FROM python:3.8\nRUN make");
+    assert_eq!(style.crlf, Some(false));
+    assert!(!style.bom);
+    assert_eq!(style.final_newline, Some(false));
+
+    // Mixed line endings and empty files carry no convention
+    assert_eq!(TextStyle::detect("a\r\nb\n").crlf, None);
+    assert_eq!(TextStyle::detect("").final_newline, None);
+}
+
+#[test]
+fn test_match_remote_style() {
+    let remote = b"\xef\xbb\xbfFROM python:3.8\r\nRUN make\r\n";
+    let local = b"FROM python:3.12\nRUN make".to_vec();
+
+    assert_eq!(
+        text_style::match_style(remote, local),
+        b"\xef\xbb\xbfFROM python:3.12\r\nRUN make\r\n"
+    );
+
+    // A remote file without a final newline keeps it that way
+    assert_eq!(
+        text_style::match_style(b"key = 1", b"key = 2\n".to_vec()),
+        b"key = 2"
+    );
+
+    // Binary content is uploaded untouched
+    let binary = vec![0x89, b'P', b'N', b'G', b'\n', 0xff];
+    assert_eq!(
+        text_style::match_style(b"a\r\nb\r\n", binary.clone()),
+        binary
+    );
+}
+
+#[test]
+fn test_preserve_style_of_edited_file() -> anyhow::Result<()> {
+    let original = "\u{feff}{\r\n  \"name\": \"app\"\r\n}";
+
+    let edited = text_style::preserve(original, true, |text| {
+        assert!(text.starts_with('{'));
+        Ok(text.replace("app", "service") + "\n")
+    })?;
+    assert_eq!(edited, "\u{feff}{\r\n  \"name\": \"service\"\r\n}");
+
+    let raw = text_style::preserve(original, false, |text| Ok(format!("{}\n", text)))?;
+    assert_eq!(raw, format!("{}\n", original));
+
+    Ok(())
+}
@@ -64,3 +64,24 @@ fn test_access_level_display() {
     assert_eq!(format!("{}", AccessLevel::Maintainer), "Maintainer");
     assert_eq!(format!("{}", AccessLevel::Owner), "Owner");
 }
+
+#[test]
+fn test_access_level_from_u64() {
+    for level in [0, 5, 10, 15, 20, 30, 40, 50] {
+        assert_eq!(AccessLevel::from_u64(level).unwrap().as_u64(), level);
+    }
+
+    assert!(AccessLevel::from_u64(25).is_none());
+    assert!(AccessLevel::from_u64(60).is_none());
+}
+
+#[test]
+fn test_access_level_ordering() {
+    assert!(AccessLevel::Owner > AccessLevel::Maintainer);
+    assert!(AccessLevel::Maintainer > AccessLevel::Developer);
+    assert!(AccessLevel::Guest > AccessLevel::MinimalAccess);
+    assert_eq!(
+        std::cmp::min(AccessLevel::Owner, AccessLevel::Developer),
+        AccessLevel::Developer
+    );
+}
@@ -0,0 +1,263 @@
+use anyhow::Result;
+use gitlab_cli::gitlab::client::GitLabClient;
+use gitlab_cli::models::member::AddedAs;
+use gitlab_cli::models::user::AccessLevel;
+use mockito::Matcher;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_get_memberships_paginates() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let first_page = server
+        .mock("GET", "/users/5/memberships")
+        .match_query(Matcher::UrlEncoded("page".into(), "1".into()))
+        .with_body(
+            json!([
+                {"source_id": 1, "source_name": "group/project", "source_type": "Project", "access_level": 30},
+                {"source_id": 2, "source_name": "group", "source_type": "Namespace", "access_level": 50}
+            ])
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let second_page = server
+        .mock("GET", "/users/5/memberships")
+        .match_query(Matcher::UrlEncoded("page".into(), "2".into()))
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let memberships = client.users().get_memberships(5).await?;
+
+    first_page.assert_async().await;
+    second_page.assert_async().await;
+
+    assert_eq!(memberships.len(), 2);
+    assert!(memberships[0].is_project());
+    assert!(!memberships[1].is_project());
+    assert_eq!(memberships[1].access_level, 50);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_project_member_not_found() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let _mock = server
+        .mock("GET", "/projects/1/members/5")
+        .with_status(404)
+        .with_body(r#"{"message":"404 Not found"}"#)
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let member = client.users().get_project_member(5, 1).await?;
+
+    assert!(member.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_remove_from_group() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("DELETE", "/groups/9/members/5")
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    client.users().remove_from_group(5, 9).await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_project_members() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let _first_page = server
+        .mock("GET", "/projects/1/members")
+        .match_query(Matcher::UrlEncoded("page".into(), "1".into()))
+        .with_body(
+            json!([
+                {"id": 5, "username": "alice", "name": "Alice", "state": "active", "access_level": 40},
+                {"id": 6, "username": "bob", "name": "Bob", "state": "blocked", "access_level": 30}
+            ])
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let _second_page = server
+        .mock("GET", "/projects/1/members")
+        .match_query(Matcher::UrlEncoded("page".into(), "2".into()))
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let members = client.users().list_project_members(1).await?;
+
+    assert_eq!(members.len(), 2);
+    assert_eq!(members[0].username, "alice");
+    assert_eq!(members[0].access_level, 40);
+    assert_eq!(members[1].state, "blocked");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_to_project_falls_back_to_invitation() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let _members = server
+        .mock("POST", "/projects/1/members")
+        .with_status(403)
+        .with_body(r#"{"message":"403 Forbidden"}"#)
+        .create_async()
+        .await;
+    let invitations = server
+        .mock("POST", "/projects/1/invitations")
+        .with_status(201)
+        .with_body(r#"{"status":"success"}"#)
+        .create_async()
+        .await;
+    let _member = server
+        .mock("GET", "/projects/1/members/5")
+        .with_status(404)
+        .with_body(r#"{"message":"404 Not found"}"#)
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let added_as = client
+        .users()
+        .add_to_project(5, 1, AccessLevel::Developer)
+        .await?;
+
+    invitations.assert_async().await;
+    assert_eq!(added_as, AddedAs::Invitation);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_to_project_invitation_that_added_member() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let _members = server
+        .mock("POST", "/projects/1/members")
+        .with_status(403)
+        .with_body(r#"{"message":"403 Forbidden"}"#)
+        .create_async()
+        .await;
+    let _invitations = server
+        .mock("POST", "/projects/1/invitations")
+        .with_status(201)
+        .with_body(r#"{"status":"success"}"#)
+        .create_async()
+        .await;
+    let _member = server
+        .mock("GET", "/projects/1/members/5")
+        .with_body(
+            r#"{"id":5,"username":"alice","name":"Alice","state":"active","access_level":30}"#,
+        )
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let added_as = client
+        .users()
+        .add_to_project(5, 1, AccessLevel::Developer)
+        .await?;
+
+    assert_eq!(added_as, AddedAs::Member);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_to_project_as_member() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let _members = server
+        .mock("POST", "/projects/1/members")
+        .with_status(201)
+        .with_body(r#"{"id":5,"access_level":30}"#)
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let added_as = client
+        .users()
+        .add_to_project(5, 1, AccessLevel::Developer)
+        .await?;
+
+    assert_eq!(added_as, AddedAs::Member);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_project_member() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("PUT", "/projects/1/members/5")
+        .match_body(Matcher::Json(json!({"access_level": 40})))
+        .with_body(
+            r#"{"id":5,"username":"alice","name":"Alice","state":"active","access_level":40}"#,
+        )
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    client
+        .users()
+        .update_project_member(5, 1, AccessLevel::Maintainer)
+        .await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_and_update_group_member() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+
+    let _get = server
+        .mock("GET", "/groups/9/members/5")
+        .with_body(
+            r#"{"id":5,"username":"alice","name":"Alice","state":"active","access_level":30}"#,
+        )
+        .create_async()
+        .await;
+    let update = server
+        .mock("PUT", "/groups/9/members/5")
+        .match_body(Matcher::Json(json!({"access_level": 50})))
+        .with_body(
+            r#"{"id":5,"username":"alice","name":"Alice","state":"active","access_level":50}"#,
+        )
+        .create_async()
+        .await;
+
+    let client = GitLabClient::new(&server.url(), "fake-token");
+    let member = client.users().get_group_member(5, 9).await?;
+    assert_eq!(member.map(|m| m.access_level), Some(30));
+
+    client
+        .users()
+        .update_group_member(5, 9, AccessLevel::Owner)
+        .await?;
+    update.assert_async().await;
+
+    Ok(())
+}